@@ -0,0 +1,121 @@
+//! Byte sequences and strings, written as literals or moved to sidecar files.
+
+use std::fmt::Write;
+
+use super::{context, Sequence};
+use crate::{BuildStr, Expr};
+
+/// Returns `true` if `len` bytes should be moved to a sidecar file.
+fn large(len: usize) -> bool {
+    context::with(|o| o.externalize).is_some_and(|min| len >= min)
+}
+
+/// Writes a byte string literal of `data`, like `b"text\0"`.
+fn literal(data: &[u8]) -> String {
+    let mut s = String::with_capacity(data.len() + 3);
+    s.push_str("b\"");
+    for &b in data {
+        match b {
+            b'\n' => s.push_str("\\n"),
+            b'\r' => s.push_str("\\r"),
+            b'\t' => s.push_str("\\t"),
+            b'\0' => s.push_str("\\0"),
+            b'\\' => s.push_str("\\\\"),
+            b'"' => s.push_str("\\\""),
+            b' '..=b'~' => s.push(char::from(b)),
+            _ => {
+                let _ = write!(s, "\\x{b:02x}");
+            }
+        }
+    }
+    s.push('"');
+    s
+}
+
+/// Writes `data` as a `&'static [u8; N]`: a byte string literal,
+/// or an `include_bytes!` of a sidecar file if it's large and they are being collected.
+#[must_use]
+pub fn byte_string(data: &[u8]) -> Expr {
+    if large(data.len()) {
+        if let Some(name) = context::externalize(data, "bin") {
+            return include("include_bytes", &name);
+        }
+    }
+    Expr::Lit(literal(data))
+}
+
+/// Includes the sidecar file `name` with the macro `include`, like `include_str!(concat!(env!("OUT_DIR"), "/name"))`.
+fn include(include: &str, name: &str) -> Expr {
+    let dir = Expr::macro_call("env", Expr::Paren(Box::new(Expr::Lit("\"OUT_DIR\"".into()))));
+    let path = Expr::macro_call("concat", Expr::tuple([dir, Expr::Lit(format!("\"/{name}\""))]));
+    Expr::macro_call(include, Expr::Paren(Box::new(path)))
+}
+
+/// Writes `s` as a `&'static str`: a string literal,
+/// or an `include_str!` of a sidecar file if it's large and they are being collected.
+#[must_use]
+pub fn string(s: &str) -> Expr {
+    include_string(s).unwrap_or_else(|| Expr::Lit(format!("{s:?}")))
+}
+
+/// Writes `s` as an `include_str!` of a sidecar file if it's large and they are being collected.
+#[must_use]
+pub fn include_string(s: &str) -> Option<Expr> {
+    if large(s.len()) {
+        let name = context::externalize(s.as_bytes(), "txt")?;
+        return Some(include("include_str", &name));
+    }
+    None
+}
+
+/// Writes `data` as a `[u8; N]`, with a byte string if [`byte_sequence`] allows it.
+#[must_use]
+pub fn byte_array(data: &[u8]) -> Expr {
+    byte_sequence(Sequence::Array, Some(data)).unwrap_or_else(|| {
+        let elements = super::render_elements(data, BuildStr::to_build_expr);
+        super::sequence(Sequence::Array, elements)
+    })
+}
+
+/// Returns `true` if `data` is written more compactly by [`sequence`](super::sequence),
+/// repeating the same byte or its runs.
+fn repeats(data: &[u8]) -> bool {
+    let (repeat, run_length) = context::with(|o| (o.repeat, o.run_length));
+    let uniform = data.len() > 1 && data.iter().all(|b| *b == data[0]);
+    if uniform && repeat.is_some_and(|min| data.len() >= min) {
+        return true;
+    }
+    run_length.is_some_and(|min| {
+        data.chunk_by(|a, b| a == b)
+            .any(|run| run.len() >= min.max(2))
+    })
+}
+
+/// Builds a sequence of bytes with a byte string, if the elements are `u8`.
+///
+/// `bytes` are the elements returned by the hidden `BuildStr::__bytes`, which only `u8` implements.
+///
+/// Returns `None` if the elements aren't bytes, [`Options::byte_strings`](crate::Options::byte_strings) is disabled,
+/// `u8` has an override, or the sequence is empty, mutable, or written more compactly by [`sequence`](super::sequence).
+#[must_use]
+pub fn byte_sequence(kind: Sequence, bytes: Option<&[u8]>) -> Option<Expr> {
+    let data = bytes?;
+    if !context::with(|o| o.byte_strings)
+        || context::has_override(std::any::TypeId::of::<u8>())
+        || data.is_empty()
+        || repeats(data)
+    {
+        return None;
+    }
+    let s = byte_string(data);
+    match kind {
+        Sequence::Array => Some(Expr::Unary {
+            op: '*',
+            expr: Box::new(s),
+        }),
+        Sequence::Slice => Some(s),
+        Sequence::SliceMut => None,
+        Sequence::Vec if super::idiomatic() => Some(s.method("to_vec", [])),
+        Sequence::Vec => Some(Expr::path("::std::vec::Vec::from").call([s])),
+    }
+}
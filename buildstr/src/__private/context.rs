@@ -0,0 +1,280 @@
+//! Per-thread state of the value currently being rendered.
+
+use std::any::TypeId;
+use std::cell::{Cell, RefCell};
+use std::time::Instant;
+
+use super::pass::Pass;
+use crate::renderer::{Override, Overrides};
+use crate::{Expr, Options, RenderError, Suffixes};
+
+struct Frame {
+    options: Options,
+    overrides: Overrides,
+    pass: Pass,
+    /// The type of the value being rendered is fixed by the enclosing value.
+    typed: bool,
+    /// First error found while rendering.
+    error: Option<RenderError>,
+    /// Sidecar files collected while rendering, see [`collect_blobs`].
+    blobs: Option<Blobs>,
+    /// Moment `Instant`s are measured from, see [`now`].
+    now: Option<Instant>,
+}
+
+/// Names and contents of sidecar files.
+pub(crate) type Files = Vec<(String, Vec<u8>)>;
+
+/// Large data written to sidecar files instead of the output.
+struct Blobs {
+    /// Prefix of the file names.
+    prefix: String,
+    files: Files,
+}
+
+thread_local! {
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+    /// Number of [`HeldNow`] alive, and the moment `Instant`s are measured from while any is held.
+    static HELD_NOW: Cell<(usize, Option<Instant>)> = const { Cell::new((0, None)) };
+}
+
+/// Keeps the moment `Instant`s are measured from outside a render pass until it's dropped, see [`hold_now`].
+pub struct HeldNow(());
+
+impl Drop for HeldNow {
+    fn drop(&mut self) {
+        HELD_NOW.with(|held| {
+            let (count, now) = held.get();
+            held.set((count - 1, now.filter(|_| count > 1)));
+        });
+    }
+}
+
+/// Measures all the `Instant`s rendered without a render pass from the same moment, until the returned
+/// [`HeldNow`] is dropped, like the ones rendered by a single call to `to_build_string`.
+#[must_use]
+pub fn hold_now() -> HeldNow {
+    HELD_NOW.with(|held| {
+        let (count, now) = held.get();
+        held.set((count + 1, now));
+    });
+    HeldNow(())
+}
+
+/// Restores the previous [`Options`] when dropped.
+pub struct Guard(());
+
+impl Guard {
+    /// Ends the render pass, resolving all the shared nodes found while rendering `expr`,
+    /// and transforming the output as requested by the options.
+    ///
+    /// # Errors
+    /// Returns the first error found while rendering.
+    pub fn finish(self, expr: Expr) -> Result<String, RenderError> {
+        self.finish_with_blobs(expr).map(|(expr, _)| expr)
+    }
+
+    /// Same as [`finish`](Self::finish), also returning the names and contents of the sidecar files
+    /// collected while rendering.
+    pub(crate) fn finish_with_blobs(self, expr: Expr) -> Result<(String, Files), RenderError> {
+        let frame = FRAMES.with(|f| {
+            f.borrow_mut().last_mut().map(|frame| {
+                (
+                    std::mem::take(&mut frame.options),
+                    std::mem::take(&mut frame.pass),
+                    frame.error.take(),
+                    frame.blobs.take().map(|b| b.files).unwrap_or_default(),
+                )
+            })
+        });
+        drop(self);
+        match frame {
+            Some((_, _, Some(error), _)) => Err(error),
+            Some((options, pass, None, blobs)) => {
+                Ok((options.transform(pass.resolve(expr)), blobs))
+            }
+            None => Ok((expr.to_string(), Vec::new())),
+        }
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        FRAMES.with(|f| f.borrow_mut().pop());
+    }
+}
+
+/// Makes `options` the current options until the returned [`Guard`] is dropped.
+#[must_use]
+pub fn enter(options: Options) -> Guard {
+    enter_with(options, Overrides::default())
+}
+
+/// Same as [`enter`], also rendering the types in `overrides` with their closure.
+#[must_use]
+pub(crate) fn enter_with(options: Options, overrides: Overrides) -> Guard {
+    FRAMES.with(|f| {
+        f.borrow_mut().push(Frame {
+            typed: options.suffixes == Suffixes::InferredTyped,
+            options,
+            overrides,
+            pass: Pass::default(),
+            error: None,
+            blobs: None,
+            now: None,
+        });
+    });
+    Guard(())
+}
+
+/// Calls `f` with the current options, or the default ones if no render is in progress.
+pub fn with<R>(f: impl FnOnce(&Options) -> R) -> R {
+    FRAMES.with(|frames| match frames.borrow().last() {
+        Some(frame) => f(&frame.options),
+        None => f(&Options::default()),
+    })
+}
+
+/// Calls `f` with the state of the current render pass.
+///
+/// Returns `None` if no render pass is in progress.
+pub(crate) fn with_pass<R>(f: impl FnOnce(&mut Pass) -> R) -> Option<R> {
+    FRAMES.with(|frames| {
+        frames
+            .borrow_mut()
+            .last_mut()
+            .map(|frame| f(&mut frame.pass))
+    })
+}
+
+/// Returns `true` if the type of the value being rendered is fixed by the enclosing value.
+pub(crate) fn typed() -> bool {
+    FRAMES.with(|frames| frames.borrow().last().is_some_and(|frame| frame.typed))
+}
+
+/// Sets whether the type of the values being rendered is fixed, returning the previous state.
+pub(crate) fn set_typed(typed: bool) -> bool {
+    FRAMES.with(|frames| {
+        frames
+            .borrow_mut()
+            .last_mut()
+            .is_some_and(|frame| std::mem::replace(&mut frame.typed, typed))
+    })
+}
+
+/// Writes the large data found while rendering to sidecar files, whose names start with `prefix`.
+///
+/// See [`Options::externalize`](crate::Options::externalize).
+pub(crate) fn collect_blobs(prefix: String) {
+    FRAMES.with(|frames| {
+        if let Some(frame) = frames.borrow_mut().last_mut() {
+            frame.blobs = Some(Blobs {
+                prefix,
+                files: Vec::new(),
+            });
+        }
+    });
+}
+
+/// Moves `data` to a sidecar file with the extension `ext`, returning its name.
+///
+/// Equal data is written to the same file. Returns `None` if the sidecar files aren't being collected.
+pub(crate) fn externalize(data: &[u8], ext: &str) -> Option<String> {
+    FRAMES.with(|frames| {
+        let mut frames = frames.borrow_mut();
+        let blobs = frames.last_mut()?.blobs.as_mut()?;
+        if let Some((name, _)) = blobs
+            .files
+            .iter()
+            .find(|(name, file)| file == data && name.ends_with(ext))
+        {
+            return Some(name.clone());
+        }
+        let name = format!("{}blob_{}.{ext}", blobs.prefix, blobs.files.len());
+        blobs.files.push((name.clone(), data.to_vec()));
+        Some(name)
+    })
+}
+
+/// Gets the moment the `Instant`s of the value being rendered are measured from,
+/// captured the first time it's needed.
+///
+/// Outside a render pass, it's kept while a [`HeldNow`] is alive.
+pub(crate) fn now() -> Instant {
+    let frame = FRAMES.with(|frames| {
+        frames
+            .borrow_mut()
+            .last_mut()
+            .map(|frame| *frame.now.get_or_insert_with(Instant::now))
+    });
+    frame.unwrap_or_else(|| {
+        HELD_NOW.with(|held| match held.get() {
+            (0, _) => Instant::now(),
+            (_, Some(now)) => now,
+            (count, None) => {
+                let now = Instant::now();
+                held.set((count, Some(now)));
+                now
+            }
+        })
+    })
+}
+
+/// Removes the override of the type `id`, so it isn't used while rendering its own value.
+pub(crate) fn take_override(id: TypeId) -> Option<Override> {
+    FRAMES.with(|frames| {
+        frames
+            .borrow_mut()
+            .last_mut()
+            .and_then(|frame| frame.overrides.types.remove(&id))
+    })
+}
+
+/// Returns `true` if the type `id` has an override in the current [`Renderer`](crate::Renderer).
+pub(crate) fn has_override(id: TypeId) -> bool {
+    FRAMES.with(|frames| {
+        frames
+            .borrow()
+            .last()
+            .is_some_and(|frame| frame.overrides.types.contains_key(&id))
+    })
+}
+
+/// Casts `error` to its concrete type, if it's registered in the current [`Renderer`](crate::Renderer).
+pub(crate) fn cast_error<'a>(
+    error: &'a (dyn std::error::Error + 'static),
+) -> Option<&'a dyn std::any::Any> {
+    let casts = FRAMES.with(|frames| {
+        frames
+            .borrow()
+            .last()
+            .map(|frame| frame.overrides.errors.clone())
+    });
+    casts?.into_iter().find_map(|cast| cast(error))
+}
+
+/// Restores an override removed by [`take_override`].
+pub(crate) fn restore_override(id: TypeId, f: Override) {
+    FRAMES.with(|frames| {
+        if let Some(frame) = frames.borrow_mut().last_mut() {
+            frame.overrides.types.insert(id, f);
+        }
+    });
+}
+
+/// Records an error found while rendering.
+///
+/// # Panics
+/// Panics with the error if no render pass is in progress, as there is no other way to report it.
+pub(crate) fn fail(error: RenderError) {
+    let error = FRAMES.with(|frames| match frames.borrow_mut().last_mut() {
+        Some(frame) => {
+            frame.error.get_or_insert(error);
+            None
+        }
+        None => Some(error),
+    });
+    if let Some(error) = error {
+        panic!("{error}");
+    }
+}
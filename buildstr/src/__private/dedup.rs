@@ -0,0 +1,35 @@
+//! Deduplication of repeated subexpressions during a render pass.
+
+use super::context;
+use super::pass::{marker, Key, Kind};
+use crate::Expr;
+
+/// Registers the rendered subexpression of a value of type `T`, so it's declared once in a `let` binding if it's repeated.
+///
+/// Expressions are only shared by values of the same type, as the same expression may build values of different types
+/// (like `::std::vec::Vec::from_iter([])`).<br>
+/// Only used if [`Options::dedup`](crate::Options::dedup) is set and the expression is at least that long.<br>
+/// Expressions that contain references are never deduplicated, as the reference can't outlive the binding.
+#[must_use]
+pub fn dedup<T: ?Sized>(_: &T, expr: Expr) -> Expr {
+    let Some(min) = context::with(|o| o.dedup) else {
+        return expr;
+    };
+    let text = expr.to_string();
+    if text.len() < min || text.contains('&') {
+        return expr;
+    }
+    let id = context::with_pass(|pass| {
+        let key = Key::Expr(std::any::type_name::<T>(), text);
+        let id = if let Some(&id) = pass.ids.get(&key) {
+            id
+        } else {
+            let id = pass.insert(key, Kind::Expr);
+            pass.complete(id, expr.clone());
+            id
+        };
+        pass.nodes[id].uses += 1;
+        id
+    });
+    id.map_or(expr, |id| marker('c', id))
+}
@@ -0,0 +1,85 @@
+//! Number literals, written as requested by the options.
+
+use std::fmt::{Debug, Display};
+
+use super::context;
+use crate::{Expr, IntFormat, Suffixes};
+
+/// Renders a value with `render`, knowing whether its type is fixed by the enclosing value.
+///
+/// Suffixes are omitted in values with a fixed type if [`Options::suffixes`](crate::Options::suffixes) allows it.
+pub fn typed<R>(typed: bool, render: impl FnOnce() -> R) -> R {
+    let previous = context::set_typed(typed);
+    let s = render();
+    context::set_typed(previous);
+    s
+}
+
+/// Returns `true` if the suffix of a number literal can be omitted.
+fn elide() -> bool {
+    context::with(|o| o.suffixes != Suffixes::Always) && context::typed()
+}
+
+/// Separates `digits` in groups of `size`, starting from the right.
+fn group(digits: &str, size: usize) -> String {
+    let mut out = String::with_capacity(digits.len() + digits.len() / size);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(size) {
+            out.push('_');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Renders an integer of the primitive type `ty` from its absolute value.
+#[must_use]
+pub fn integer(negative: bool, abs: u128, ty: &str) -> Expr {
+    let (format, separators) = context::with(|o| (o.int_format, o.digit_separators));
+    let (prefix, digits, size) = match format {
+        IntFormat::Decimal => ("", abs.to_string(), 3),
+        IntFormat::Hex => ("0x", format!("{abs:x}"), 4),
+        IntFormat::Binary => ("0b", format!("{abs:b}"), 4),
+    };
+    let digits = if separators {
+        group(&digits, size)
+    } else {
+        digits
+    };
+    let suffix = match (elide(), format) {
+        (true, _) => String::new(),
+        // `0xff_u8` is easier to read than `0xffu8`
+        (false, IntFormat::Decimal) if !separators => ty.to_owned(),
+        (false, _) => format!("_{ty}"),
+    };
+    let lit = Expr::Lit(format!("{prefix}{digits}{suffix}"));
+    if negative {
+        Expr::Unary {
+            op: '-',
+            expr: Box::new(lit),
+        }
+    } else {
+        lit
+    }
+}
+
+/// Renders a float of the primitive type `ty`.
+#[must_use]
+pub fn float<T: Debug + Display>(value: T, ty: &str) -> Expr {
+    let debug = format!("{value:?}");
+    let lit = match debug.as_str() {
+        "NaN" => return Expr::path(&format!("::core::primitive::{ty}::NAN")),
+        "inf" => return Expr::path(&format!("::core::primitive::{ty}::INFINITY")),
+        "-inf" => return Expr::path(&format!("::core::primitive::{ty}::NEG_INFINITY")),
+        // The debug format always has a decimal point or an exponent, so it's a float literal
+        _ if elide() => debug,
+        _ => format!("{value}{ty}"),
+    };
+    match lit.strip_prefix('-') {
+        Some(abs) => Expr::Unary {
+            op: '-',
+            expr: Box::new(Expr::Lit(abs.to_owned())),
+        },
+        None => Expr::Lit(lit),
+    }
+}
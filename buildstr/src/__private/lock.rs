@@ -0,0 +1,34 @@
+//! Locks (`Mutex`, `RwLock`), rendered with the value they protect.
+
+use std::sync::TryLockResult;
+
+use super::{context, track};
+use crate::{Expr, Poison, RenderError};
+
+/// Renders the value protected by a lock with `render`, from the result of trying to lock it.
+///
+/// A poisoned lock is rendered as a lock that isn't poisoned, or fails with [`RenderError::Poisoned`],
+/// as requested by [`Options::poison`](crate::Options::poison).<br>
+/// A lock that is held, by this thread or another one, fails with [`RenderError::Locked`],
+/// as waiting for it could never end.
+pub fn locked<G>(result: TryLockResult<G>, render: impl FnOnce(&G) -> Expr) -> Expr {
+    let guard = match result {
+        Ok(guard) => guard,
+        Err(std::sync::TryLockError::Poisoned(error)) => {
+            if context::with(|o| o.poison) == Poison::Fail {
+                context::fail(RenderError::Poisoned {
+                    path: track::current(),
+                });
+                return Expr::Verbatim(String::new());
+            }
+            error.into_inner()
+        }
+        Err(std::sync::TryLockError::WouldBlock) => {
+            context::fail(RenderError::Locked {
+                path: track::current(),
+            });
+            return Expr::Verbatim(String::new());
+        }
+    };
+    render(&guard)
+}
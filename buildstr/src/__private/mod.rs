@@ -1,16 +1,133 @@
-#[cfg(feature = "pretty")]
+#[cfg(feature = "proc-macro")]
+pub use proc_macro2::TokenStream;
+
+use crate::Expr;
+
 #[doc(hidden)]
-pub fn __pretty(code: impl AsRef<str>) -> String {
-    let expr = syn::parse_str(code.as_ref()).unwrap();
-    prettier_please::unparse_expr(&expr)
+#[must_use]
+pub fn deterministic() -> bool {
+    context::with(|o| o.deterministic)
 }
 
-#[cfg(feature = "proc-macro")]
-pub use proc_macro2::TokenStream;
+/// Returns `true` if the current [`Style`](crate::Style) uses the idiomatic forms, like `vec![]`.
+#[doc(hidden)]
+#[must_use]
+pub fn idiomatic() -> bool {
+    context::with(|o| o.style != crate::Style::Canonical)
+}
 
-#[cfg(feature = "proc-macro")]
-pub fn __str_to_tokens(s: String) -> TokenStream {
-    s.parse().unwrap()
+/// Writes a reference to the rendered value `expr`, or leaks it if requested by
+/// [`Options::references`](crate::Options::references), failing with [`RenderError::Leaked`](crate::RenderError::Leaked)
+/// if it refers to a shared value.
+#[doc(hidden)]
+#[must_use]
+pub fn reference(mutable: bool, expr: Expr) -> Expr {
+    let leak = context::with(|o| o.references == crate::References::Leak)
+        && (mutable || !sequence::is_copy(&expr));
+    if leak {
+        // Shared values are bound to a variable, which can't be moved
+        if pass::has_marker(&expr) {
+            context::fail(crate::RenderError::Leaked {
+                path: track::current(),
+            });
+            return Expr::Verbatim(String::new());
+        }
+        let (leak, new) = if idiomatic() {
+            ("Box::leak", "Box::new")
+        } else {
+            ("::std::boxed::Box::leak", "::std::boxed::Box::new")
+        };
+        Expr::path(leak).call([Expr::path(new).call([expr])])
+    } else {
+        expr.reference(mutable)
+    }
 }
 
-pub(crate) mod unescape;
\ No newline at end of file
+/// Gets the final form of a rendered value, to sort the entries of unordered collections.
+///
+/// Values deduplicated or shared in the current pass are inlined, so they compare by their content.
+#[doc(hidden)]
+#[must_use]
+pub fn sort_key(expr: &Expr) -> String {
+    context::with_pass(|pass| pass.expand(expr).to_string()).unwrap_or_else(|| expr.to_string())
+}
+
+/// Gets the path of a derived type named `name`, defined in the module `module` (from `module_path!`).
+///
+/// The path is relative to the crate root if [`Options::qualify_types`](crate::Options::qualify_types) is enabled,
+/// and the type isn't defined inside a function, where it can't be named from its module.
+/// `type_name` is the full name of the type, used to tell them apart.
+#[doc(hidden)]
+#[must_use]
+pub fn type_path(module: &str, name: &str, type_name: &str) -> String {
+    if !context::with(|o| o.qualify_types) {
+        return name.to_owned();
+    }
+    let type_name = type_name.split('<').next().unwrap_or(type_name);
+    if type_name.strip_prefix(module) != Some(&format!("::{name}")) {
+        return name.to_owned();
+    }
+    match module.split_once("::") {
+        Some((_, module)) => format!("crate::{module}::{name}"),
+        None => format!("crate::{name}"),
+    }
+}
+
+/// Renders `value` with the override of its type in the current [`Renderer`](crate::Renderer), or with `render`.
+pub fn render_static<T: 'static>(value: &T, render: impl FnOnce() -> Expr) -> Expr {
+    let id = std::any::TypeId::of::<T>();
+    match context::take_override(id) {
+        Some(f) => {
+            // The override may put the nested values anywhere, so their type isn't known
+            let s = typed(false, || f(value));
+            context::restore_override(id, f);
+            Expr::Verbatim(s)
+        }
+        None => render(),
+    }
+}
+
+/// Renders `value` with the override of its concrete type in the current [`Renderer`](crate::Renderer),
+/// failing with [`RenderError::Unregistered`](crate::RenderError::Unregistered) if there is none.
+///
+/// `name` is the type of the trait object, like `dyn Any`.
+pub fn render_any(value: &dyn std::any::Any, name: &'static str) -> Expr {
+    let id = value.type_id();
+    let Some(f) = context::take_override(id) else {
+        let mut path = track::current();
+        path.push(name);
+        context::fail(crate::RenderError::Unregistered { path });
+        return Expr::Verbatim(String::new());
+    };
+    // The type of the concrete value is erased by the coercion
+    let s = typed(false, || f(value));
+    context::restore_override(id, f);
+    Expr::Verbatim(s)
+}
+
+/// Fails with the error built by `error` from the path of the value of type `T`.
+pub(crate) fn fail<T>(error: impl FnOnce(Vec<&'static str>) -> crate::RenderError) -> Expr {
+    let mut path = track::current();
+    path.push(std::any::type_name::<T>());
+    context::fail(error(path));
+    Expr::Verbatim(String::new())
+}
+
+pub mod bytes;
+pub mod context;
+pub mod dedup;
+pub mod literal;
+pub mod lock;
+pub(crate) mod pass;
+pub mod sequence;
+pub mod shared;
+pub mod track;
+
+pub use bytes::{byte_array, byte_sequence, byte_string, include_string, string};
+pub use dedup::dedup;
+pub use literal::{float, integer, typed};
+pub use lock::locked;
+pub use sequence::{chunked, render_elements, sequence, Sequence};
+pub use shared::{shared, shared_dyn, shared_from, weak};
+pub use track::{nested, visit};
+pub(crate) mod unescape;
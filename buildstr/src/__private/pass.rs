@@ -0,0 +1,285 @@
+//! State of a render pass started by [`render_with`](crate::render_with).
+//!
+//! Values that may be hoisted into a binding (shared pointers, repeated subexpressions) are rendered as markers
+//! that point to a [`Node`], and resolved once the whole value is rendered.<br>
+//! Nodes used only once are inlined back, while the ones used more than once are declared in a `let` binding.
+//!
+//! Names are given in the order the nodes appear in the final output, so they don't depend on the order
+//! the value was rendered (like the iteration order of a `HashMap`).
+
+use std::collections::HashMap;
+
+use crate::expr::{Expr, Stmt};
+
+/// Delimits a marker in the rendered string.
+pub(crate) const MARKER: char = '\u{1}';
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Key {
+    /// A shared pointer and the name of its type.
+    Ptr(*const (), &'static str),
+    /// A rendered subexpression and the name of its type.
+    Expr(&'static str, String),
+}
+
+pub(crate) enum Kind {
+    /// A shared pointer, with the path of its type like `::std::rc::Rc`, and how it's built.
+    Pointer(&'static str, Constructor),
+    /// A subexpression, cloned at each use.
+    Expr,
+}
+
+pub(crate) struct Node {
+    pub(crate) key: Option<Key>,
+    pub(crate) kind: Kind,
+    /// Rendered value (the pointee for pointers), available once it's complete.
+    pub(crate) inner: Expr,
+    pub(crate) uses: usize,
+    /// A weak pointer to this node was found while rendering its pointee.
+    pub(crate) cyclic: bool,
+    /// Number of completed nodes when this one was found.
+    pub(crate) start: usize,
+    /// Cyclic node whose closure must contain the declaration of this node.
+    pub(crate) parent: Option<usize>,
+}
+
+#[derive(Default)]
+pub(crate) struct Pass {
+    pub(crate) ids: HashMap<Key, usize>,
+    pub(crate) nodes: Vec<Node>,
+    /// Ids of the nodes in the order they were completed.
+    pub(crate) order: Vec<usize>,
+}
+
+/// How a shared pointer is built from its pointee.
+#[derive(Clone, Copy)]
+pub(crate) enum Constructor {
+    /// `::std::rc::Rc::new(inner)`.
+    New,
+    /// `::std::rc::Rc::<str>::from(inner)`, with the unsized pointee type.
+    From(&'static str),
+    /// `::std::rc::Rc::new(inner) as ::std::rc::Rc<dyn Trait>`, with the trait object type.
+    Dyn(&'static str),
+}
+
+/// Builds a shared pointer of type `path` that owns `inner`.
+pub(crate) fn constructor(path: &str, constructor: Constructor, inner: Expr) -> Expr {
+    match constructor {
+        Constructor::New => Expr::path(&format!("{path}::new")).call([inner]),
+        Constructor::From(ty) => Expr::path(&format!("{path}::<{ty}>::from")).call([inner]),
+        Constructor::Dyn(ty) => Expr::path(&format!("{path}::new"))
+            .call([inner])
+            .cast(format!("{path}<{ty}>")),
+    }
+}
+
+/// Marker pointing to the node `id`.
+///
+/// `kind` is `s` for a strong use, `r` for a place expression (`&{marker}`), `c` for a cloned subexpression
+/// and `w` for the weak pointer given to the closure of a cyclic node.
+pub(crate) fn marker(kind: char, id: usize) -> Expr {
+    Expr::Verbatim(format!("{MARKER}{kind}{id}{MARKER}"))
+}
+
+/// Returns `true` if `expr` contains a marker, so it refers to the nodes of the current pass.
+pub(crate) fn has_marker(expr: &Expr) -> bool {
+    crate::expr::compact(expr).contains(MARKER)
+}
+
+/// Splits the marker at the start of `s` into its kind and node, returning the rest of `s`.
+fn split_marker(s: &str) -> (char, usize, &str) {
+    let marker = &s[MARKER.len_utf8()..];
+    let end = marker.find(MARKER).expect("unterminated marker");
+    let mut chars = marker[..end].chars();
+    let kind = chars.next().expect("invalid marker");
+    let id = chars.as_str().parse().expect("invalid marker");
+    (kind, id, &marker[end + MARKER.len_utf8()..])
+}
+
+impl Pass {
+    /// Adds a new node, not yet complete.
+    pub(crate) fn insert(&mut self, key: Key, kind: Kind) -> usize {
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            key: Some(key.clone()),
+            kind,
+            inner: Expr::Verbatim(String::new()),
+            uses: 0,
+            cyclic: false,
+            start: self.order.len(),
+            parent: None,
+        });
+        self.ids.insert(key, id);
+        id
+    }
+
+    /// Completes the node `id` with its rendered value.
+    pub(crate) fn complete(&mut self, id: usize, inner: Expr) {
+        let node = &mut self.nodes[id];
+        node.inner = inner;
+        if node.cyclic {
+            // The children are only in scope inside of the closure, so they can't be used anymore.
+            for child in self.order.split_off(node.start) {
+                let child = &mut self.nodes[child];
+                child.parent = Some(id);
+                if let Some(key) = child.key.take() {
+                    self.ids.remove(&key);
+                }
+            }
+        }
+        self.order.push(id);
+    }
+
+    pub(crate) fn resolve(&self, expr: Expr) -> Expr {
+        let mut resolver = Resolver {
+            pass: self,
+            inline: false,
+            names: HashMap::new(),
+            weak_names: HashMap::new(),
+            scopes: vec![(None, Vec::new())],
+            counts: (0, 0),
+        };
+        let expr = resolver.substitute(expr);
+        match resolver.scopes.pop() {
+            Some((_, lets)) if !lets.is_empty() => Expr::block(lets, expr),
+            _ => expr,
+        }
+    }
+
+    /// Inlines all the markers in `expr`, to compare rendered values independently of the nodes found.
+    pub(crate) fn expand(&self, expr: &Expr) -> Expr {
+        let mut resolver = Resolver {
+            pass: self,
+            inline: true,
+            names: HashMap::new(),
+            weak_names: HashMap::new(),
+            scopes: vec![(None, Vec::new())],
+            counts: (0, 0),
+        };
+        resolver.substitute(expr.clone())
+    }
+}
+
+struct Resolver<'a> {
+    pass: &'a Pass,
+    /// Inline all nodes, even the ones used more than once.
+    inline: bool,
+    names: HashMap<usize, String>,
+    /// Names of the weak pointers given to the closures of cyclic nodes.
+    weak_names: HashMap<usize, String>,
+    /// `let` bindings of each scope, with the cyclic node that opened it.
+    scopes: Vec<(Option<usize>, Vec<Stmt>)>,
+    /// Number of pointers and subexpressions declared.
+    counts: (usize, usize),
+}
+
+impl Resolver<'_> {
+    /// Replaces the markers in `expr` with the nodes they point to.
+    ///
+    /// Markers in [`Expr::Verbatim`] code (like the output of an override) are replaced with the printed node.
+    fn substitute(&mut self, mut expr: Expr) -> Expr {
+        if let Expr::Verbatim(code) = &expr {
+            if !code.contains(MARKER) {
+                return expr;
+            }
+            if code.starts_with(MARKER) {
+                let (kind, id, rest) = split_marker(code);
+                if rest.is_empty() {
+                    return self.marker(kind, id);
+                }
+            }
+            let mut out = String::with_capacity(code.len());
+            let mut rest = code.as_str();
+            while let Some(start) = rest.find(MARKER) {
+                out.push_str(&rest[..start]);
+                let (kind, id, next) = split_marker(&rest[start..]);
+                out.push_str(&self.marker(kind, id).to_string());
+                rest = next;
+            }
+            out.push_str(rest);
+            return Expr::Verbatim(out);
+        }
+        expr.for_each_child_mut(&mut |child| {
+            let inner = std::mem::replace(child, Expr::Verbatim(String::new()));
+            *child = self.substitute(inner);
+        });
+        expr
+    }
+
+    /// Gets the expression that replaces the marker of `kind` pointing to the node `id`.
+    fn marker(&mut self, kind: char, id: usize) -> Expr {
+        if kind == 'w' {
+            return Expr::path(&self.weak_names[&id]);
+        }
+        self.name(id);
+        match (self.names.get(&id), &self.pass.nodes[id].kind, kind) {
+            (Some(name), Kind::Pointer(path, _), 's') => {
+                Expr::path(&format!("{path}::clone")).call([Expr::path(name).reference(false)])
+            }
+            (Some(name), Kind::Expr, _) => Expr::path("::core::clone::Clone::clone")
+                .call([Expr::path(name).reference(false)]),
+            (Some(name), _, _) => Expr::path(name),
+            (None, _, _) => self.build(id),
+        }
+    }
+
+    /// Declares the node `id` if it's used more than once and isn't declared yet.
+    fn name(&mut self, id: usize) {
+        let node = &self.pass.nodes[id];
+        if self.inline || node.uses < 2 || self.names.contains_key(&id) {
+            return;
+        }
+        let value = self.build(id);
+        let name = match node.kind {
+            Kind::Pointer(..) => {
+                self.counts.0 += 1;
+                format!("__n{}", self.counts.0 - 1)
+            }
+            Kind::Expr => {
+                self.counts.1 += 1;
+                format!("__c{}", self.counts.1 - 1)
+            }
+        };
+        let scope = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find(|(owner, _)| owner.is_none() || *owner == node.parent)
+            .expect("the outermost scope always exists");
+        scope.1.push(Stmt::Let {
+            mutable: false,
+            name: name.clone(),
+            ty: None,
+            init: value,
+        });
+        self.names.insert(id, name);
+    }
+
+    fn build(&mut self, id: usize) -> Expr {
+        let node = &self.pass.nodes[id];
+        match node.kind {
+            Kind::Pointer(path, _) if node.cyclic => {
+                let weak = format!("__w{}", self.weak_names.len());
+                self.weak_names.insert(id, weak.clone());
+                self.scopes.push((Some(id), Vec::new()));
+                let inner = self.substitute(node.inner.clone());
+                let (_, lets) = self.scopes.pop().expect("the scope was just pushed");
+                let body = if lets.is_empty() {
+                    inner
+                } else {
+                    Expr::block(lets, inner)
+                };
+                let closure = Expr::Closure {
+                    params: vec![weak],
+                    body: Box::new(body),
+                };
+                Expr::path(&format!("{path}::new_cyclic")).call([closure])
+            }
+            Kind::Pointer(path, ctor) => {
+                let inner = self.substitute(node.inner.clone());
+                constructor(path, ctor, inner)
+            }
+            Kind::Expr => self.substitute(node.inner.clone()),
+        }
+    }
+}
@@ -0,0 +1,415 @@
+//! Sequences of elements (arrays, slices and vectors), written compactly when their elements repeat.
+
+use std::collections::HashSet;
+
+use super::context;
+use crate::{Expr, Stmt, Suffixes};
+
+/// Kind of sequence built by [`sequence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sequence {
+    /// `[T; N]`
+    Array,
+    /// `&[T]`
+    Slice,
+    /// `&mut [T]`
+    SliceMut,
+    /// `Vec<T>`
+    Vec,
+}
+
+/// Renders the elements of a sequence with `render`.
+///
+/// A number literal whose suffix is written in an element fixes the type of the literals at the same
+/// position in the next elements, so they may omit their suffixes (see [`Options::suffixes`](crate::Options::suffixes)).
+pub fn render_elements<I: IntoIterator>(
+    items: I,
+    mut render: impl FnMut(I::Item) -> Expr,
+) -> Vec<Expr> {
+    let elide = context::with(|o| o.suffixes != Suffixes::Always);
+    let mut suffixed = HashSet::new();
+    items
+        .into_iter()
+        .map(|item| {
+            let mut expr = render(item);
+            if elide {
+                elide_suffixes(&mut expr, "", &mut suffixed);
+            }
+            expr
+        })
+        .collect()
+}
+
+/// Omits the suffixes of the number literals of `expr` whose `position` is in `suffixed`,
+/// and adds the positions of the literals that keep their suffix.
+///
+/// The position of a literal is the path of the constructors and indices that lead to it.
+/// The values whose type isn't fixed by their position, like casts and method calls, are skipped.
+fn elide_suffixes(expr: &mut Expr, position: &str, suffixed: &mut HashSet<String>) {
+    if let Some(ty) = literal_type(expr).filter(|_| is_number(expr)) {
+        if let Expr::Lit(lit) = expr {
+            if suffixed.contains(position) {
+                *lit = strip_suffix(lit, &ty);
+            } else {
+                suffixed.insert(position.to_owned());
+            }
+        }
+        return;
+    }
+    let children: Vec<(String, &mut Expr)> = match expr {
+        Expr::Call { func, args, .. } => {
+            let func = func.to_string();
+            let args = args.iter_mut().enumerate();
+            args.map(|(i, arg)| (format!("{func}({i}"), arg)).collect()
+        }
+        Expr::StructLit { path, fields, .. } => fields
+            .iter_mut()
+            .map(|(name, value)| (format!("{path}{{{name}"), value))
+            .collect(),
+        Expr::TupleLit { items, .. } => items
+            .iter_mut()
+            .enumerate()
+            .map(|(i, item)| (format!("({i}"), item))
+            .collect(),
+        Expr::Array(items) => items
+            .iter_mut()
+            .enumerate()
+            .map(|(i, item)| (format!("[{i}"), item))
+            .collect(),
+        Expr::Repeat { expr, .. } => vec![("[;".into(), &mut **expr)],
+        Expr::Ref { expr, .. } => vec![("&".into(), &mut **expr)],
+        // Negative numbers have the type of the number
+        Expr::Unary { expr, .. } | Expr::Paren(expr) => {
+            return elide_suffixes(expr, position, suffixed);
+        }
+        Expr::Macro { path, args } => vec![(format!("{path}!"), &mut **args)],
+        Expr::Range { start, end, .. } => {
+            let start = start.iter_mut().map(|start| ("..0".into(), &mut **start));
+            start
+                .chain(end.iter_mut().map(|end| ("..1".into(), &mut **end)))
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+    for (label, child) in children {
+        elide_suffixes(child, &format!("{position}/{label}"), suffixed);
+    }
+}
+
+/// Returns `true` if `expr` is a number literal.
+fn is_number(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(lit) if lit.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Removes the suffix `ty` of the number literal `lit`, keeping a decimal point in floats.
+fn strip_suffix(lit: &str, ty: &str) -> String {
+    let digits = lit.strip_suffix(ty).unwrap_or(lit);
+    let digits = digits.strip_suffix('_').unwrap_or(digits);
+    if ty.starts_with('f') && !digits.contains(['.', 'e', 'E']) {
+        format!("{digits}.0")
+    } else {
+        digits.to_owned()
+    }
+}
+
+/// Returns `true` if the rendered elements `a` and `b` are the same value.
+///
+/// The first element may keep its suffixes while the others omit them, so they are compared without them.
+fn same(a: &Expr, b: &Expr) -> bool {
+    a == b || unsuffixed(a) == unsuffixed(b)
+}
+
+/// Writes the value of a number literal, ignoring its suffix and format.
+fn number_value(lit: &str) -> Option<String> {
+    const INTS: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    if !lit.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let lit = lit.replace('_', "");
+    let (radix, digits) = match lit.get(..2) {
+        Some("0x") => (16, &lit[2..]),
+        Some("0o") => (8, &lit[2..]),
+        Some("0b") => (2, &lit[2..]),
+        _ => (10, &lit[..]),
+    };
+    let int_digits = INTS.iter().find_map(|suffix| digits.strip_suffix(suffix));
+    let float_digits = (radix == 10)
+        .then(|| {
+            ["f32", "f64"]
+                .iter()
+                .find_map(|suffix| digits.strip_suffix(suffix))
+        })
+        .flatten();
+    let int = |digits| {
+        u128::from_str_radix(digits, radix)
+            .ok()
+            .map(|v| v.to_string())
+    };
+    let float = |digits: &str| digits.parse::<f64>().ok().map(|v| format!("{v:?}"));
+    match (int_digits, float_digits) {
+        (Some(digits), _) => int(digits),
+        (None, Some(digits)) => float(digits),
+        (None, None) if radix == 10 && digits.contains(['.', 'e', 'E']) => float(digits),
+        (None, None) => int(digits),
+    }
+}
+
+/// Copies `expr`, writing its number literals as their value, so they are compared without their suffix.
+fn unsuffixed(expr: &Expr) -> Expr {
+    fn strip(expr: &mut Expr) {
+        if let Expr::Lit(lit) = expr {
+            if let Some(value) = number_value(lit) {
+                *lit = value;
+            }
+        }
+        expr.for_each_child_mut(&mut strip);
+    }
+    let mut expr = expr.clone();
+    strip(&mut expr);
+    expr
+}
+
+/// Returns `true` if `expr` only contains literals, so its value is `Copy` and can be repeated with `[expr; N]`.
+pub(crate) fn is_copy(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Unary { expr, .. }
+        | Expr::Paren(expr)
+        | Expr::Repeat { expr, .. }
+        | Expr::Ref {
+            mutable: false,
+            expr,
+        } => is_copy(expr),
+        Expr::TupleLit { items, .. } | Expr::Array(items) => items.iter().all(is_copy),
+        _ => false,
+    }
+}
+
+fn len(len: usize) -> Expr {
+    Expr::Lit(len.to_string())
+}
+
+/// An iterator that yields `expr` `len` times.
+fn repeat(expr: &Expr, len: usize) -> Expr {
+    if is_copy(expr) {
+        Expr::Repeat {
+            expr: Box::new(expr.clone()),
+            len: Box::new(self::len(len)),
+        }
+    } else {
+        let closure = Expr::Closure {
+            params: Vec::new(),
+            body: Box::new(expr.clone()),
+        };
+        Expr::path("::core::iter::repeat_with")
+            .call([closure])
+            .method("take", [self::len(len)])
+    }
+}
+
+/// An iterator that yields the elements, chaining the runs of at least `min` equal elements,
+/// or `None` if there are no such runs.
+fn runs(elements: &[Expr], min: usize) -> Option<Expr> {
+    let mut parts = Vec::new();
+    let mut list = Vec::new();
+    let mut repeated = false;
+    let mut i = 0;
+    while i < elements.len() {
+        let len = elements[i..]
+            .iter()
+            .take_while(|e| same(e, &elements[i]))
+            .count();
+        if len >= min {
+            if !list.is_empty() {
+                parts.push(Expr::Array(std::mem::take(&mut list)));
+            }
+            parts.push(repeat(&elements[i], len));
+            repeated = true;
+        } else {
+            list.extend_from_slice(&elements[i..i + len]);
+        }
+        i += len;
+    }
+    if !repeated {
+        return None;
+    }
+    if !list.is_empty() {
+        parts.push(Expr::Array(list));
+    }
+    let mut parts = parts.into_iter();
+    let mut iter = Expr::path("::core::iter::IntoIterator::into_iter").call([parts.next()?]);
+    for part in parts {
+        iter = iter.method("chain", [part]);
+    }
+    Some(iter)
+}
+
+/// Returns `true` if all the `elements` are equal and there are enough of them to be repeated.
+fn repeated(elements: &[Expr]) -> bool {
+    let len = elements.len();
+    let uniform = len > 1
+        && elements[1..].iter().all(|e| *e == elements[1])
+        && same(&elements[0], &elements[1]);
+    uniform && context::with(|o| o.repeat).is_some_and(|min| len >= min)
+}
+
+/// Gets the type of `expr` from its literals, like `(u8, [char; 2])` for `(1u8, ['a', 'b'])`,
+/// or `None` if it contains other expressions or literals without suffixes.
+fn literal_type(expr: &Expr) -> Option<String> {
+    const NUMBERS: &[&str] = &[
+        "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64",
+        "u128", "usize",
+    ];
+    match expr {
+        Expr::Lit(lit) if lit.starts_with('\'') => Some("char".into()),
+        Expr::Lit(lit) if lit == "true" || lit == "false" => Some("bool".into()),
+        Expr::Lit(lit) if lit.starts_with(|c: char| c.is_ascii_digit()) => {
+            // Hexadecimal digits may look like a float suffix, as in `0x1f32`
+            let hex = lit.starts_with("0x");
+            NUMBERS
+                .iter()
+                .filter(|ty| !hex || !ty.starts_with('f'))
+                .find(|ty| lit.ends_with(**ty))
+                .map(|ty| (*ty).to_string())
+        }
+        Expr::Unary { op: '-', expr } | Expr::Paren(expr) => literal_type(expr),
+        Expr::TupleLit { items, .. } => {
+            let types = items.iter().map(literal_type).collect::<Option<Vec<_>>>()?;
+            match types.as_slice() {
+                [ty] => Some(format!("({ty},)")),
+                _ => Some(format!("({})", types.join(", "))),
+            }
+        }
+        Expr::Array(items) => Some(format!(
+            "[{}; {}]",
+            literal_type(items.first()?)?,
+            items.len()
+        )),
+        Expr::Repeat { expr, len } => Some(format!("[{}; {len}]", literal_type(expr)?)),
+        _ => None,
+    }
+}
+
+/// Builds a `Vec<T>` of the rendered `elements` in chunks of [`Options::chunk_size`](crate::Options::chunk_size),
+/// or returns `None` if there aren't more elements than that or they are repeated.
+///
+/// Chunks of literals are declared as `const` items, so each one is checked on its own,
+/// and copied into the vector. Their type is read from the suffixes of the first element,
+/// so `const` items are only used if it has them.
+///
+/// Other chunks are appended to the vector one at a time, but they are still checked as part of the whole block,
+/// so chunking only helps vectors of primitive types.
+#[must_use]
+pub fn chunked(elements: &[Expr]) -> Option<Expr> {
+    let size = context::with(|o| o.chunk_size)?.max(1);
+    if elements.len() <= size || repeated(elements) {
+        return None;
+    }
+    // Constants can't refer to the bindings of shared and deduplicated values
+    let ty = literal_type(&elements[0]).filter(|_| {
+        elements
+            .iter()
+            .all(|e| is_copy(e) && !super::pass::has_marker(e))
+    });
+
+    let vec = if super::idiomatic() {
+        "Vec"
+    } else {
+        "::std::vec::Vec"
+    };
+    let items = || Expr::path("items");
+    let mut consts = Vec::new();
+    let mut fill = Vec::new();
+    for (i, chunk) in elements.chunks(size).enumerate() {
+        let list = Expr::Array(chunk.to_vec());
+        if let Some(ty) = &ty {
+            let name = format!("__CHUNK{i}");
+            let copy = Expr::path(&name).reference(false);
+            consts.push(Stmt::Const {
+                name,
+                ty: format!("[{ty}; {}]", chunk.len()),
+                init: list,
+            });
+            fill.push(Stmt::Expr(items().method("extend_from_slice", [copy])));
+        } else {
+            let chunk = Expr::path(&format!("{vec}::from")).call([list]);
+            fill.push(Stmt::Expr(
+                items().method("append", [chunk.reference(true)]),
+            ));
+        }
+    }
+    consts.push(Stmt::Let {
+        mutable: true,
+        name: "items".into(),
+        ty: None,
+        init: Expr::path(&format!("{vec}::with_capacity")).call([len(elements.len())]),
+    });
+    consts.extend(fill);
+    Some(Expr::block(consts, items()))
+}
+
+/// Builds a sequence of the rendered `elements`.
+///
+/// If all the elements are equal and there are at least [`Options::repeat`](crate::Options::repeat) of them,
+/// the element is repeated, like `[0u8; 4096]`. Otherwise, if [`Options::run_length`](crate::Options::run_length)
+/// is set, arrays and vectors chain the runs of equal elements.
+#[must_use]
+pub fn sequence(kind: Sequence, elements: Vec<Expr>) -> Expr {
+    let run_min = context::with(|o| o.run_length);
+    let count = elements.len();
+    let from_fn = || Expr::path(&format!("::core::array::from_fn::<_, {count}, _>"));
+    if repeated(&elements) {
+        let expr = &elements[0];
+        let array = if is_copy(expr) {
+            repeat(expr, count)
+        } else {
+            from_fn().call([Expr::Closure {
+                params: vec!["_".into()],
+                body: Box::new(expr.clone()),
+            }])
+        };
+        return match kind {
+            Sequence::Array => array,
+            Sequence::Slice | Sequence::SliceMut => {
+                super::reference(kind == Sequence::SliceMut, array)
+            }
+            Sequence::Vec if is_copy(expr) && super::idiomatic() => Expr::macro_call("vec", array),
+            Sequence::Vec if is_copy(expr) => Expr::macro_call("::std::vec", array),
+            Sequence::Vec => Expr::path("::std::vec::Vec::from_iter").call([repeat(expr, count)]),
+        };
+    }
+
+    let runs = match kind {
+        Sequence::Array | Sequence::Vec => run_min.and_then(|min| runs(&elements, min.max(2))),
+        Sequence::Slice | Sequence::SliceMut => None,
+    };
+    match (kind, runs) {
+        (Sequence::Array, Some(iter)) => {
+            let next = Expr::path("iter").method("next", []).method("unwrap", []);
+            let array = from_fn().call([Expr::Closure {
+                params: vec!["_".into()],
+                body: Box::new(next),
+            }]);
+            let iter = Stmt::Let {
+                mutable: true,
+                name: "iter".into(),
+                ty: None,
+                init: iter,
+            };
+            Expr::block(vec![iter], array)
+        }
+        (Sequence::Vec, Some(iter)) => Expr::path("::std::vec::Vec::from_iter").call([iter]),
+        (kind, _) => {
+            let list = Expr::Array(elements);
+            match kind {
+                Sequence::Array => list,
+                Sequence::Slice => super::reference(false, list),
+                Sequence::SliceMut => super::reference(true, list),
+                Sequence::Vec if super::idiomatic() => Expr::macro_call("vec", list),
+                Sequence::Vec => Expr::path("::std::vec::Vec::from_iter").call([list]),
+            }
+        }
+    }
+}
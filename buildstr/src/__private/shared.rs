@@ -0,0 +1,133 @@
+//! Tracking of shared pointers (`Rc`, `Arc`) during a render pass.
+//!
+//! All the uses of the same pointer are rendered as clones of a single binding.<br>
+//! A weak pointer to a value that is still being rendered is a back-pointer of a cycle,
+//! so the value is rebuilt with `new_cyclic`, and the weak pointer is a clone of the one given to the closure.
+
+use super::pass::{constructor, marker, Constructor, Key, Kind};
+use super::{context, track};
+use crate::Expr;
+
+enum Seen {
+    First(usize),
+    Again(usize),
+    Visiting(usize),
+}
+
+/// Registers a use of `ptr` in the current pass.
+///
+/// Returns `None` if pointers are not shared.
+fn register(
+    ptr: *const (),
+    name: &'static str,
+    path: &'static str,
+    ctor: Constructor,
+) -> Option<Seen> {
+    if !context::with(|o| o.share_pointers) {
+        return None;
+    }
+    context::with_pass(|pass| {
+        let key = Key::Ptr(ptr, name);
+        match pass.ids.get(&key) {
+            None => Some(Seen::First(pass.insert(key, Kind::Pointer(path, ctor)))),
+            Some(&id) if track::is_visiting(ptr, name) => Some(Seen::Visiting(id)),
+            Some(&id) => Some(Seen::Again(id)),
+        }
+        .inspect(|seen| {
+            if let Seen::First(id) | Seen::Again(id) = *seen {
+                pass.nodes[id].uses += 1;
+            }
+        })
+    })
+    .flatten()
+}
+
+/// Renders the pointee of a node seen for the first time, and completes it.
+fn complete(id: usize, ptr: *const (), name: &'static str, inner: impl FnOnce() -> Expr) {
+    let inner = track::visit(Some(ptr), name, inner);
+    context::with_pass(|pass| pass.complete(id, inner));
+}
+
+/// Renders a shared pointer, like `::std::rc::Rc::new(inner)`.
+///
+/// When rendered with [`render_with`](crate::render_with), all the uses of the same pointer are rendered as
+/// clones of a single binding.
+pub fn shared<T: ?Sized>(
+    ptr: *const T,
+    name: &'static str,
+    path: &'static str,
+    inner: impl FnOnce() -> Expr,
+) -> Expr {
+    share(ptr.cast(), name, path, Constructor::New, inner)
+}
+
+/// Same as [`shared`], for pointers to the unsized type `ty`, like `::std::rc::Rc::<str>::from(inner)`.
+pub fn shared_from<T: ?Sized>(
+    ptr: *const T,
+    name: &'static str,
+    path: &'static str,
+    ty: &'static str,
+    inner: impl FnOnce() -> Expr,
+) -> Expr {
+    share(ptr.cast(), name, path, Constructor::From(ty), inner)
+}
+
+/// Same as [`shared`], for pointers to the trait object `ty`, like
+/// `::std::rc::Rc::new(inner) as ::std::rc::Rc<dyn Trait>`.
+pub fn shared_dyn<T: ?Sized>(
+    ptr: *const T,
+    name: &'static str,
+    path: &'static str,
+    ty: &'static str,
+    inner: impl FnOnce() -> Expr,
+) -> Expr {
+    share(ptr.cast(), name, path, Constructor::Dyn(ty), inner)
+}
+
+fn share(
+    ptr: *const (),
+    name: &'static str,
+    path: &'static str,
+    ctor: Constructor,
+    inner: impl FnOnce() -> Expr,
+) -> Expr {
+    match register(ptr, name, path, ctor) {
+        None => track::visit(Some(ptr), name, || constructor(path, ctor, inner())),
+        Some(Seen::Visiting(_)) => {
+            context::fail(track::cycle(ptr, name));
+            Expr::Verbatim(String::new())
+        }
+        Some(Seen::Again(id)) => marker('s', id),
+        Some(Seen::First(id)) => {
+            complete(id, ptr, name, inner);
+            marker('s', id)
+        }
+    }
+}
+
+/// Renders a weak pointer to the shared pointer `ptr`, like `::std::rc::Rc::downgrade(&::std::rc::Rc::new(inner))`.
+///
+/// If the shared pointer is being rendered, the weak pointer is a back-pointer of a cycle,
+/// and it's rendered as a clone of the weak pointer given by `new_cyclic`.
+pub fn weak<T: ?Sized>(
+    ptr: *const T,
+    name: &'static str,
+    path: &'static str,
+    weak_path: &'static str,
+    inner: impl FnOnce() -> Expr,
+) -> Expr {
+    let ptr = ptr.cast::<()>();
+    let shared = match register(ptr, name, path, Constructor::New) {
+        None => track::visit(Some(ptr), name, || constructor(path, Constructor::New, inner())),
+        Some(Seen::Visiting(id)) => {
+            context::with_pass(|pass| pass.nodes[id].cyclic = true);
+            return Expr::path(&format!("{weak_path}::clone")).call([marker('w', id)]);
+        }
+        Some(Seen::Again(id)) => marker('r', id),
+        Some(Seen::First(id)) => {
+            complete(id, ptr, name, inner);
+            marker('r', id)
+        }
+    };
+    Expr::path(&format!("{path}::downgrade")).call([shared.reference(false)])
+}
@@ -0,0 +1,78 @@
+//! Tracking of the values currently being rendered, to detect cycles and limit the depth.
+
+use std::cell::RefCell;
+
+use super::context;
+use crate::{Expr, RenderError};
+
+thread_local! {
+    static STACK: RefCell<Vec<(*const (), &'static str)>> = const { RefCell::new(Vec::new()) };
+}
+
+struct Pop;
+
+impl Drop for Pop {
+    fn drop(&mut self) {
+        STACK.with(|s| s.borrow_mut().pop());
+    }
+}
+
+/// Gets the names of the values being rendered, from the position of `ptr` (or the start) until `name`.
+fn path(from: Option<(*const (), &'static str)>, name: &'static str) -> Vec<&'static str> {
+    STACK.with(|s| {
+        let s = s.borrow();
+        let start = from
+            .and_then(|from| s.iter().position(|v| *v == from))
+            .unwrap_or(0);
+        s[start..].iter().map(|(_, n)| *n).chain([name]).collect()
+    })
+}
+
+/// Gets the names of the values being rendered, from the outermost one.
+pub(crate) fn current() -> Vec<&'static str> {
+    STACK.with(|s| s.borrow().iter().map(|(_, n)| *n).collect())
+}
+
+/// Returns `true` if the value `ptr` of type `name` is currently being rendered.
+pub(crate) fn is_visiting(ptr: *const (), name: &'static str) -> bool {
+    STACK.with(|s| s.borrow().contains(&(ptr, name)))
+}
+
+/// Builds a [`RenderError::Cycle`] that ends in the value `ptr` of type `name`.
+pub(crate) fn cycle(ptr: *const (), name: &'static str) -> RenderError {
+    RenderError::Cycle {
+        path: path(Some((ptr, name)), name),
+    }
+}
+
+/// Renders a value with `f`, failing if it's already being rendered or if the maximum depth is exceeded.
+///
+/// `ptr` identifies values that can be part of a cycle, like `Rc` or `RefCell`, and `name` is the type of the value.
+pub fn visit<T: ?Sized>(
+    ptr: Option<*const T>,
+    name: &'static str,
+    f: impl FnOnce() -> Expr,
+) -> Expr {
+    let ptr = ptr.map_or(std::ptr::null(), <*const T>::cast::<()>);
+    if !ptr.is_null() && is_visiting(ptr, name) {
+        context::fail(cycle(ptr, name));
+        return Expr::Verbatim(String::new());
+    }
+    let depth = STACK.with(|s| s.borrow().len());
+    if let Some(limit) = context::with(|o| o.max_depth).filter(|limit| depth >= *limit) {
+        context::fail(RenderError::DepthLimit {
+            limit,
+            path: path(None, name),
+        });
+        return Expr::Verbatim(String::new());
+    }
+
+    STACK.with(|s| s.borrow_mut().push((ptr, name)));
+    let _pop = Pop;
+    f()
+}
+
+/// Same as [`visit`], for values that can't be part of a cycle, like derived types.
+pub fn nested(name: &'static str, f: impl FnOnce() -> Expr) -> Expr {
+    visit(None::<*const ()>, name, f)
+}
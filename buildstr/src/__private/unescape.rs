@@ -43,7 +43,14 @@ impl ::std::fmt::Display for Error {
     }
 }
 
-impl ::std::error::Error for Error {}
+impl ::std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+        match self {
+            Error::ParseInt { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 /// Unescaper struct that holds the chars cache for unescaping.
 #[derive(Debug)]
@@ -1,16 +1,22 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for ::std::alloc::Layout {
-    fn to_build_string(&self) -> String {
-        let size = self.size();
-        let align = self.align();
-        // SAFETY: The Layout is valid because the original one is
-        format!("unsafe{{Self::from_size_align_unchecked({size},{align})}}")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let size = Expr::Lit(self.size().to_string());
+            let align = Expr::Lit(self.align().to_string());
+            // SAFETY: The Layout is valid because the original one is
+            Expr::Block {
+                unsafety: true,
+                stmts: Vec::new(),
+                expr: Some(Box::new(Expr::path("Self::from_size_align_unchecked").call([size, align]))),
+            }
+        })
     }
 }
 
 impl BuildStr for ::std::alloc::System {
-    fn to_build_string(&self) -> String {
-        "::std::alloc::System".into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::path("::std::alloc::System"))
     }
 }
\ No newline at end of file
@@ -0,0 +1,33 @@
+use std::any::Any;
+
+use crate::{BuildStr, Expr};
+
+impl BuildStr for dyn Any {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_any(self, "dyn ::std::any::Any")
+    }
+
+    fn __dyn_type(&self) -> Option<&'static str> {
+        Some("dyn ::std::any::Any")
+    }
+}
+
+impl BuildStr for dyn Any + Send {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_any(self, "dyn ::std::any::Any + Send")
+    }
+
+    fn __dyn_type(&self) -> Option<&'static str> {
+        Some("dyn ::std::any::Any + Send")
+    }
+}
+
+impl BuildStr for dyn Any + Send + Sync {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_any(self, "dyn ::std::any::Any + Send + Sync")
+    }
+
+    fn __dyn_type(&self) -> Option<&'static str> {
+        Some("dyn ::std::any::Any + Send + Sync")
+    }
+}
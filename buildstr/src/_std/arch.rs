@@ -1,4 +1,4 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr, Layout, Path};
 
 #[cfg(target_arch = "x86_64")]
 mod x86_64 {
@@ -7,12 +7,21 @@ mod x86_64 {
 
     #[allow(clippy::similar_names)]
     impl BuildStr for ::std::arch::x86_64::CpuidResult {
-        fn to_build_string(&self) -> String {
-            let eax = self.eax;
-            let ebx = self.ebx;
-            let ecx = self.ecx;
-            let edx = self.edx;
-            format!("::std::arch::x86_64::CpuidResult{{eax:{eax},ebx:{ebx},ecx:{ecx},edx:{edx}}}")
+        fn to_build_expr(&self) -> Expr {
+            crate::__private::render_static::<Self>(self, || {
+                let eax = self.eax;
+                let ebx = self.ebx;
+                let ecx = self.ecx;
+                let edx = self.edx;
+                Expr::StructLit {
+                    path: Path::parse("::std::arch::x86_64::CpuidResult"),
+                    fields: [("eax", eax), ("ebx", ebx), ("ecx", ecx), ("edx", edx)]
+                        .into_iter()
+                        .map(|(name, value)| (name.to_owned(), Expr::Lit(value.to_string())))
+                        .collect(),
+                    layout: Layout::Compact,
+                }
+            })
         }
     }
 }
@@ -24,12 +33,21 @@ mod x86 {
 
     #[allow(clippy::similar_names)]
     impl BuildStr for ::std::arch::x86::CpuidResult {
-        fn to_build_string(&self) -> String {
-            let eax = self.eax;
-            let ebx = self.ebx;
-            let ecx = self.ecx;
-            let edx = self.edx;
-            format!("::std::arch::x86::CpuidResult{{eax:{eax},ebx:{ebx},ecx:{ecx},edx:{edx}}}")
+        fn to_build_expr(&self) -> Expr {
+            crate::__private::render_static::<Self>(self, || {
+                let eax = self.eax;
+                let ebx = self.ebx;
+                let ecx = self.ecx;
+                let edx = self.edx;
+                Expr::StructLit {
+                    path: Path::parse("::std::arch::x86::CpuidResult"),
+                    fields: [("eax", eax), ("ebx", ebx), ("ecx", ecx), ("edx", edx)]
+                        .into_iter()
+                        .map(|(name, value)| (name.to_owned(), Expr::Lit(value.to_string())))
+                        .collect(),
+                    layout: Layout::Compact,
+                }
+            })
         }
     }
 }
\ No newline at end of file
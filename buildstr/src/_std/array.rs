@@ -1,15 +1,24 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr, Layout};
 
 impl<T: BuildStr, const N: usize> BuildStr for ::std::array::IntoIter<T, N> {
-    fn to_build_string(&self) -> String {
-        let iter = self.as_slice();
-        let v = buildstr::array_to_build_string!(iter);
-        format!("([{v}]as[{};{N}]).into_iter()", ::std::any::type_name::<T>())
+    fn to_build_expr(&self) -> Expr {
+        let elements = crate::__private::render_elements(self.as_slice(), |x| {
+            crate::__private::dedup(x, x.to_build_expr())
+        });
+        Expr::Array(elements)
+            .cast(format!("[{};{N}]", ::std::any::type_name::<T>()))
+            .with_layout(Layout::Compact)
+            .method("into_iter", [])
     }
 }
 
 impl BuildStr for ::std::array::TryFromSliceError {
-    fn to_build_string(&self) -> String {
-        "::core::convert::TryInto::<[();1]>::try_into(&[]as&[()]).unwrap_err()".into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let empty = Expr::array([]).reference(false).cast("&[()]").with_layout(Layout::Compact);
+            Expr::path("::core::convert::TryInto::<[();1]>::try_into")
+                .call([empty])
+                .method("unwrap_err", [])
+        })
     }
 }
\ No newline at end of file
@@ -1,9 +1,11 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for ::std::ascii::EscapeDefault {
-    fn to_build_string(&self) -> String {
-        let s = crate::__private::unescape::unescape(self.to_string()).unwrap();
-        let c = s.parse::<char>().expect(&s) as u8;
-        format!("::std::ascii::escape_default({c})")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = crate::__private::unescape::unescape(self.to_string()).unwrap();
+            let c = s.parse::<char>().expect(&s) as u8;
+            Expr::path("::std::ascii::escape_default").call([Expr::Lit(c.to_string())])
+        })
     }
 }
\ No newline at end of file
@@ -0,0 +1,25 @@
+use crate::{BuildStr, Expr};
+
+impl BuildStr for Box<str> {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("Box::<str>::from").call([(&**self).to_build_expr()])
+        })
+    }
+}
+
+impl BuildStr for Box<::std::ffi::OsStr> {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("Box::<::std::ffi::OsStr>::from").call([(&**self).to_build_expr()])
+        })
+    }
+}
+
+impl BuildStr for Box<::std::path::Path> {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("Box::<::std::path::Path>::from").call([(&**self).to_build_expr()])
+        })
+    }
+}
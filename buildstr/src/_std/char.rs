@@ -1,13 +1,17 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for std::char::CharTryFromError {
-    fn to_build_string(&self) -> String {
-        "::std::primitive::char::try_from(::std::primitive::u32::MAX).unwrap_err()".into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::primitive::char::try_from")
+                .call([Expr::path("::std::primitive::u32::MAX")])
+                .method("unwrap_err", [])
+        })
     }
 }
 
 impl<I: std::iter::Iterator<Item = u16> + Clone> BuildStr for std::char::DecodeUtf16<I> {
-    fn to_build_string(&self) -> String {
+    fn to_build_expr(&self) -> Expr {
         let mut chars = Vec::new();
         for c in self.clone() {
             match c {
@@ -19,103 +23,115 @@ impl<I: std::iter::Iterator<Item = u16> + Clone> BuildStr for std::char::DecodeU
                 Err(err) => chars.push(err.unpaired_surrogate()),
             }
         }
-        let chars = buildstr::array_to_build_string!(chars);
-        format!("::std::primitive::char::decode_utf16([{chars}])")
+        let chars = crate::__private::render_elements(chars.iter(), |x| {
+            crate::__private::dedup(x, x.to_build_expr())
+        });
+        Expr::path("::std::primitive::char::decode_utf16").call([Expr::Array(chars)])
     }
 }
 
 impl BuildStr for std::char::DecodeUtf16Error {
-    fn to_build_string(&self) -> String {
-        let code = self.unpaired_surrogate();
-        format!("::std::primitive::char::decode_utf16([{code}u16]).next().unwrap().unwrap_err()")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let code = self.unpaired_surrogate();
+            Expr::Verbatim(format!("::std::primitive::char::decode_utf16([{code}u16]).next().unwrap().unwrap_err()"))
+        })
     }
 }
 
 impl BuildStr for std::char::EscapeDebug {
-    fn to_build_string(&self) -> String {
-        let c = crate::__private::unescape::to_char(self.to_string())
-            .unwrap()
-            .to_build_string();
-        format!("::std::primitive::char::escape_debug({c})")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let c = crate::__private::unescape::to_char(self.to_string())
+                .unwrap()
+                .to_build_expr();
+            Expr::path("::std::primitive::char::escape_debug").call([c])
+        })
     }
 }
 
 impl BuildStr for std::char::EscapeDefault {
-    fn to_build_string(&self) -> String {
-        let c = crate::__private::unescape::to_char(self.to_string())
-            .unwrap()
-            .to_build_string();
-        format!("::std::primitive::char::escape_default({c})")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let c = crate::__private::unescape::to_char(self.to_string())
+                .unwrap()
+                .to_build_expr();
+            Expr::path("::std::primitive::char::escape_default").call([c])
+        })
     }
 }
 
 impl BuildStr for std::char::EscapeUnicode {
-    fn to_build_string(&self) -> String {
-        let c = crate::__private::unescape::to_char(self.to_string())
-            .unwrap()
-            .to_build_string();
-        format!("::std::primitive::char::escape_unicode({c})")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let c = crate::__private::unescape::to_char(self.to_string())
+                .unwrap()
+                .to_build_expr();
+            Expr::path("::std::primitive::char::escape_unicode").call([c])
+        })
     }
 }
 
 impl BuildStr for std::char::ParseCharError {
-    fn to_build_string(&self) -> String {
-        let s = self.to_string();
-        match s.as_str() {
-            "cannot parse char from empty string" => {
-                "<::std::primitive::char as ::std::str::FromStr>::from_str(\"\").unwrap_err()"
-            }
-            "too many characters in string" => {
-                "<::std::primitive::char as ::std::str::FromStr>::from_str(\"aa\").unwrap_err()"
-            }
-            _ => unreachable!("::std::char::CharErrorKind doesn't have more variants. {s}"),
-        }
-        .into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = self.to_string();
+            let s = match s.as_str() {
+                "cannot parse char from empty string" => "\"\"",
+                "too many characters in string" => "\"aa\"",
+                _ => unreachable!("::std::char::CharErrorKind doesn't have more variants. {s}"),
+            };
+            Expr::path("<::std::primitive::char as ::std::str::FromStr>::from_str")
+                .call([Expr::Lit(s.into())])
+                .method("unwrap_err", [])
+        })
     }
 }
 
 impl BuildStr for std::char::ToLowercase {
-    fn to_build_string(&self) -> String {
-        let s = self.to_string();
-        if let Ok(c) = s.parse::<char>() {
-            return format!(
-                "::std::primitive::char::to_lowercase({})",
-                c.to_build_string()
-            );
-        }
-        
-        // Based on `core::unicode::unicode_data::conversions::LOWERCASE_TABLE_MULTI`
-        match s.as_str() {
-            "i\u{307}" => "::std::primitive::char::to_lowercase('İ')".into(),
-            _ => panic!("{s:?} is not handled. Please, open an issue at https://github.com/lyonsyonii/buildstr.")
-        }
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = self.to_string();
+            if let Ok(c) = s.parse::<char>() {
+                return Expr::path("::std::primitive::char::to_lowercase").call([c.to_build_expr()]);
+            }
+
+            // Based on `core::unicode::unicode_data::conversions::LOWERCASE_TABLE_MULTI`
+            match s.as_str() {
+                "i\u{307}" => Expr::path("::std::primitive::char::to_lowercase").call([Expr::Lit("'İ'".into())]),
+                _ => panic!("{s:?} is not handled. Please, open an issue at https://github.com/lyonsyonii/buildstr.")
+            }
+        })
     }
 }
 
 impl BuildStr for std::char::ToUppercase {
-    fn to_build_string(&self) -> String {
-        let s = self.to_string();
-        if let Ok(c) = s.parse::<char>() {
-            return format!(
-                "::std::primitive::char::to_uppercase({})",
-                c.to_build_string()
-            );
-        }
-        
-        // Based on `core::unicode::unicode_data::conversions::UPPERCASE_TABLE_MULTI`
-        let mut chars = self.clone().collect::<Vec<_>>();
-        chars.resize(3, '\0');
-        let i = UPPERCASE_TABLE.binary_search_by(|(v, _)| v.as_slice().cmp(chars.as_slice()))
-            .unwrap_or_else(|_| panic!("{chars:?} is not handled. Please, open an issue at https://github.com/lyonsyonii/buildstr."));
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = self.to_string();
+            if let Ok(c) = s.parse::<char>() {
+                return Expr::path("::std::primitive::char::to_uppercase").call([c.to_build_expr()]);
+            }
+
+            // Based on `core::unicode::unicode_data::conversions::UPPERCASE_TABLE_MULTI`
+            let mut chars = self.clone().collect::<Vec<_>>();
+            chars.resize(3, '\0');
+            let i = UPPERCASE_TABLE.binary_search_by(|(v, _)| v.as_slice().cmp(chars.as_slice()))
+                .unwrap_or_else(|_| panic!("{chars:?} is not handled. Please, open an issue at https://github.com/lyonsyonii/buildstr."));
 
-        let c = UPPERCASE_TABLE[i].1.to_build_string();
-        format!("::std::primitive::char::to_uppercase({c})")
+            let c = UPPERCASE_TABLE[i].1.to_build_expr();
+            Expr::path("::std::primitive::char::to_uppercase").call([c])
+        })
     }
 }
 
 impl BuildStr for std::char::TryFromCharError {
-    fn to_build_string(&self) -> String {
-        "::std::primitive::u8::try_from('\\u{f00}').unwrap_err()".into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::primitive::u8::try_from")
+                .call([Expr::Lit("'\\u{f00}'".into())])
+                .method("unwrap_err", [])
+        })
     }
 }
 
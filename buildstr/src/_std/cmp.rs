@@ -1,11 +1,13 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for ::core::cmp::Ordering {
-    fn to_build_string(&self) -> String {
-        match self {
-            ::std::cmp::Ordering::Less => "::core::cmp::Ordering::Less",
-            ::std::cmp::Ordering::Equal => "::core::cmp::Ordering::Equal",
-            ::std::cmp::Ordering::Greater => "::core::cmp::Ordering::Greater",
-        }.into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path(match self {
+                ::std::cmp::Ordering::Less => "::core::cmp::Ordering::Less",
+                ::std::cmp::Ordering::Equal => "::core::cmp::Ordering::Equal",
+                ::std::cmp::Ordering::Greater => "::core::cmp::Ordering::Greater",
+            })
+        })
     }
 }
\ No newline at end of file
@@ -1,12 +1,24 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for std::collections::TryReserveError {
-    fn to_build_string(&self) -> String {
-        let s = self.to_string();
-        match s.as_str() {
-            "memory allocation failed because the computed capacity exceeded the collection's maximum" => "::std::vec::Vec::<u8>::with_capacity(1).try_reserve_exact(::std::primitive::usize::MAX).unwrap_err()",
-            "memory allocation failed because the memory allocator returned an error" => todo!("AllocError kind not implemented, please open an issue at https://github.com/lyonsyonii/buildstr."),
-            _ => todo!("{s:?} case is not handled. Please, open an issue at https://github.com/lyonsyonii/buildstr.")
-        }.into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = self.to_string();
+            match s.as_str() {
+                "memory allocation failed because the computed capacity exceeded the collection's maximum" => Expr::path("::std::vec::Vec::<u8>::with_capacity")
+                    .call([Expr::Lit("1".into())])
+                    .method("try_reserve_exact", [Expr::path("::std::primitive::usize::MAX")])
+                    .method("unwrap_err", []),
+                "memory allocation failed because the memory allocator returned an error" => todo!("AllocError kind not implemented, please open an issue at https://github.com/lyonsyonii/buildstr."),
+                _ => todo!("{s:?} case is not handled. Please, open an issue at https://github.com/lyonsyonii/buildstr.")
+            }
+        })
     }
-}
\ No newline at end of file
+}
+impl BuildStr for ::std::collections::hash_map::RandomState {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::collections::hash_map::RandomState::new").call([])
+        })
+    }
+}
@@ -1,7 +1,9 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for std::env::JoinPathsError {
-    fn to_build_string(&self) -> String {
-        r#"::std::env::join_paths([":\""]).unwrap_err()"#.into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::Verbatim(r#"::std::env::join_paths([":\""]).unwrap_err()"#.into())
+        })
     }
 }
\ No newline at end of file
@@ -1,8 +1,89 @@
-use crate::BuildStr;
+use std::error::Error;
 
-impl BuildStr for &dyn std::error::Error {
-    fn to_build_string(&self) -> String {
-        let s = self.to_string();
-        format!(r#"<&::std::primitive::str as ::std::convert::Into<::std::boxed::Box<dyn ::std::error::Error>>>::into("{s}").as_ref()"#)
+use crate::{BuildStr, Expr, Layout};
+
+/// Renders `error` if it's one of the error types of the standard library.
+pub(crate) fn known(error: &(dyn Error + 'static)) -> Option<Expr> {
+    macro_rules! known {
+        ($($ty:ty),* $(,)?) => {
+            $(
+                if let Some(error) = error.downcast_ref::<$ty>() {
+                    return Some(error.to_build_expr());
+                }
+            )*
+        };
+    }
+
+    known!(
+        ::core::num::ParseIntError,
+        ::core::num::ParseFloatError,
+        ::core::num::TryFromIntError,
+        ::core::str::ParseBoolError,
+        ::core::str::Utf8Error,
+        ::std::string::FromUtf8Error,
+        ::std::char::CharTryFromError,
+        ::std::char::DecodeUtf16Error,
+        ::std::char::ParseCharError,
+        ::std::char::TryFromCharError,
+        ::std::array::TryFromSliceError,
+        ::std::fmt::Error,
+        ::std::ffi::FromBytesWithNulError,
+        ::std::ffi::FromVecWithNulError,
+        ::std::ffi::IntoStringError,
+        ::std::ffi::NulError,
+        ::std::sync::mpsc::RecvError,
+        ::std::sync::mpsc::TryRecvError,
+        ::std::sync::mpsc::RecvTimeoutError,
+        ::std::collections::TryReserveError,
+        ::std::net::AddrParseError,
+        ::std::env::JoinPathsError,
+        ::std::time::SystemTimeError,
+        ::core::time::TryFromFloatSecsError,
+        ::std::io::Error,
+    );
+    None
+}
+
+/// Renders the concrete value of `error`: an error of `std`, an error registered in the current
+/// [`Renderer`](crate::Renderer), or an [`ErrorMessage`](crate::ErrorMessage) with the same message and sources.
+///
+/// `name` is the type of the trait object, like `dyn Error`.
+fn render(error: &(dyn Error + 'static), name: &'static str) -> Expr {
+    if let Some(error) = known(error) {
+        return error;
+    }
+    if let Some(value) = crate::__private::context::cast_error(error) {
+        return crate::__private::render_any(value, name);
     }
-}
\ No newline at end of file
+    let message = error.to_string();
+    let message = message.as_str().to_build_expr();
+    match error.source() {
+        Some(source) => Expr::path("::buildstr::ErrorMessage::with_source")
+            .call([message, render(source, name)])
+            .with_layout(Layout::Spaced),
+        None => Expr::path("::buildstr::ErrorMessage::new").call([message]),
+    }
+}
+
+macro_rules! dyn_error {
+    ($($ty:literal => $($bound:ident)*),*) => {
+        $(
+            impl BuildStr for dyn Error $(+ $bound)* {
+                fn to_build_expr(&self) -> Expr {
+                    // The type of the concrete value is erased by the coercion
+                    crate::__private::typed(false, || render(self, $ty))
+                }
+
+                fn __dyn_type(&self) -> Option<&'static str> {
+                    Some($ty)
+                }
+            }
+        )*
+    };
+}
+
+dyn_error!(
+    "dyn ::std::error::Error" =>,
+    "dyn ::std::error::Error + Send" => Send,
+    "dyn ::std::error::Error + Send + Sync" => Send Sync
+);
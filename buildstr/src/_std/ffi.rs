@@ -1,71 +1,125 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
+// Written as a `&CStr`, which references to it keep as it is
 impl BuildStr for ::std::ffi::CStr {
-    fn to_build_string(&self) -> String {
-        format!(
-            "::std::ffi::CStr::from_bytes_with_nul({}).unwrap()",
-            self.to_bytes_with_nul().to_build_string()
-        )
+    fn to_build_expr(&self) -> Expr {
+        Expr::path("::std::ffi::CStr::from_bytes_with_nul")
+            .call([self.to_bytes_with_nul().to_build_expr()])
+            .method("unwrap", [])
+    }
+    fn __unsized_type(&self) -> Option<&'static str> {
+        Some("::std::ffi::CStr")
     }
 }
 
 impl BuildStr for ::std::ffi::CString {
-    fn to_build_string(&self) -> String {
-        let b = buildstr::array_to_build_string!(self.to_bytes());
-        format!("::std::ffi::CString::new([{b}]).unwrap()")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let b = crate::__private::byte_array(self.as_bytes());
+            Expr::path("::std::ffi::CString::new").call([b]).method("unwrap", [])
+        })
     }
 }
 
+/// Renders `s` as a `&'static str` if it's valid UTF-8,
+/// or as a `&'static OsStr` of its raw bytes otherwise, so file names round-trip exactly.
+pub(crate) fn os_str(s: &::std::ffi::OsStr) -> Expr {
+    match s.to_str() {
+        Some(s) => crate::__private::string(s),
+        None => raw(s),
+    }
+}
+
+#[cfg(unix)]
+fn raw(s: &::std::ffi::OsStr) -> Expr {
+    use std::os::unix::ffi::OsStrExt;
+
+    Expr::path("<::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes")
+        .call([crate::__private::byte_string(s.as_bytes())])
+}
+
+// There's no portable way to build an `OsStr` from its raw encoding, so the name is written lossily
+#[cfg(not(unix))]
+fn raw(s: &::std::ffi::OsStr) -> Expr {
+    crate::__private::string(&s.to_string_lossy())
+}
+
 impl BuildStr for &::std::ffi::OsStr {
-    fn to_build_string(&self) -> String {
-        format!("::std::ffi::OsStr::new({self:?})")
+    fn to_build_expr(&self) -> Expr {
+        match self.to_str() {
+            Some(s) => Expr::path("::std::ffi::OsStr::new").call([crate::__private::string(s)]),
+            None => raw(self),
+        }
     }
 }
 
 impl BuildStr for ::std::ffi::OsString {
-    fn to_build_string(&self) -> String {
-        format!("::std::ffi::OsString::from({self:?})")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::ffi::OsString::from").call([os_str(self)])
+        })
     }
 }
 
 impl BuildStr for std::ffi::FromBytesWithNulError {
-    fn to_build_string(&self) -> String {
-        // 1. data provided contains an interior nul byte at byte pos X
-        // 2. data provided is not nul terminated
-        let s = self
-            .to_string()
-            .split_once("pos ")
-            .map(|(_, s)| format!("{}\0a", "a".repeat(s.parse().unwrap())))
-            .unwrap_or_default();
-        format!("::std::ffi::CStr::from_bytes_with_nul(\"{s}\").unwrap_err()")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            // 1. data provided contains an interior nul byte at byte pos X
+            // 2. data provided is not nul terminated
+            let s = self
+                .to_string()
+                .split_once("pos")
+                .and_then(|(_, s)| s.rsplit(' ').next()?.parse().ok())
+                .map(|pos| format!("{}\0a", "a".repeat(pos)))
+                .unwrap_or_default();
+            Expr::path("::std::ffi::CStr::from_bytes_with_nul")
+                .call([Expr::Lit(format!("\"{s}\""))])
+                .method("unwrap_err", [])
+        })
     }
 }
 
 impl BuildStr for std::ffi::FromVecWithNulError {
-    fn to_build_string(&self) -> String {
-        let v = self.as_bytes().to_vec().to_build_string();
-        format!("::std::ffi::CString::from_vec_with_nul({v}).unwrap_err()")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let v = self.as_bytes().to_vec().to_build_expr();
+            Expr::path("::std::ffi::CString::from_vec_with_nul")
+                .call([v])
+                .method("unwrap_err", [])
+        })
     }
 }
 
 impl BuildStr for std::ffi::IntoStringError {
-    fn to_build_string(&self) -> String {
-        let s = self.clone().into_cstring().to_build_string();
-        format!("{s}.into_string().unwrap_err()")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = self.clone().into_cstring().to_build_expr();
+            s.method("into_string", []).method("unwrap_err", [])
+        })
     }
 }
 
 impl BuildStr for std::ffi::NulError {
-    fn to_build_string(&self) -> String {
-        let v = buildstr::array_to_build_string!(self.clone().into_vec());
-        format!("::std::ffi::CString::new([{v}]).unwrap_err()")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let v = crate::__private::byte_array(&self.clone().into_vec());
+            Expr::path("::std::ffi::CString::new").call([v]).method("unwrap_err", [])
+        })
     }
 }
 
 impl BuildStr for std::ffi::c_void {
-    fn to_build_string(&self) -> String {
-        // SAFETY: std::ffi::c_void is repr(u8)
-        let n: u8 = unsafe { std::mem::transmute_copy(self) };
-        format!("unsafe{{std::mem::transmute::<u8,::std::ffi::c_void>({n})}}")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            // SAFETY: std::ffi::c_void is repr(u8)
+            let n: u8 = unsafe { std::mem::transmute_copy(self) };
+            Expr::Block {
+                unsafety: true,
+                stmts: Vec::new(),
+                expr: Some(Box::new(
+                    Expr::path("std::mem::transmute::<u8,::std::ffi::c_void>").call([Expr::Lit(n.to_string())]),
+                )),
+            }
+        })
     }
 }
\ No newline at end of file
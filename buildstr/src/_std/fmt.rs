@@ -1,24 +1,25 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for ::core::fmt::Arguments<'_> {
-    fn to_build_string(&self) -> String {
-        format!("::core::format_args!(\"{self}\")")
+    fn to_build_expr(&self) -> Expr {
+        Expr::macro_call("::core::format_args", Expr::Paren(Box::new(Expr::Lit(format!("\"{self}\"")))))
     }
 }
 
 impl BuildStr for ::std::fmt::Error {
-    fn to_build_string(&self) -> String {
-        "::std::fmt::Error".into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::path("::std::fmt::Error"))
     }
 }
 
 impl BuildStr for ::core::fmt::Alignment {
-    fn to_build_string(&self) -> String {
-        match self {
-            ::core::fmt::Alignment::Left => "::core::fmt::Alignment::Left",
-            ::core::fmt::Alignment::Right => "::core::fmt::Alignment::Right",
-            ::core::fmt::Alignment::Center => "::core::fmt::Alignment::Center",
-        }
-        .to_string()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path(match self {
+                ::core::fmt::Alignment::Left => "::core::fmt::Alignment::Left",
+                ::core::fmt::Alignment::Right => "::core::fmt::Alignment::Right",
+                ::core::fmt::Alignment::Center => "::core::fmt::Alignment::Center",
+            })
+        })
     }
 }
\ No newline at end of file
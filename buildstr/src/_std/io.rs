@@ -0,0 +1,128 @@
+use std::io::{Empty, Error, ErrorKind, SeekFrom, Sink, Stderr, Stdin, Stdout};
+
+use crate::{BuildStr, Expr, Layout};
+
+/// Stable variants of [`ErrorKind`], matched by name so they don't depend on the version of `std`.
+static ERROR_KINDS: &[&str] = &[
+    "NotFound",
+    "PermissionDenied",
+    "ConnectionRefused",
+    "ConnectionReset",
+    "HostUnreachable",
+    "NetworkUnreachable",
+    "ConnectionAborted",
+    "NotConnected",
+    "AddrInUse",
+    "AddrNotAvailable",
+    "NetworkDown",
+    "BrokenPipe",
+    "AlreadyExists",
+    "WouldBlock",
+    "NotADirectory",
+    "IsADirectory",
+    "DirectoryNotEmpty",
+    "ReadOnlyFilesystem",
+    "StaleNetworkFileHandle",
+    "InvalidInput",
+    "InvalidData",
+    "TimedOut",
+    "WriteZero",
+    "StorageFull",
+    "NotSeekable",
+    "QuotaExceeded",
+    "FileTooLarge",
+    "ResourceBusy",
+    "ExecutableFileBusy",
+    "Deadlock",
+    "CrossesDevices",
+    "TooManyLinks",
+    "InvalidFilename",
+    "ArgumentListTooLong",
+    "Interrupted",
+    "Unsupported",
+    "UnexpectedEof",
+    "OutOfMemory",
+    "Other",
+];
+
+impl BuildStr for ErrorKind {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let name = format!("{self:?}");
+            if ERROR_KINDS.contains(&name.as_str()) {
+                Expr::path(&format!("::std::io::ErrorKind::{name}"))
+            } else {
+                // Unstable kinds, like `Uncategorized`, can't be named
+                Expr::path("::std::io::ErrorKind::Other")
+            }
+        })
+    }
+}
+
+impl BuildStr for Error {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            if let Some(code) = self.raw_os_error() {
+                return Expr::path("::std::io::Error::from_raw_os_error").call([Expr::Lit(code.to_string())]);
+            }
+            let kind = self.kind();
+            let inner = self.get_ref();
+            if let Some(error) = inner.and_then(|e| super::error::known(e)) {
+                return Expr::path("::std::io::Error::new")
+                    .call([kind.to_build_expr(), error])
+                    .with_layout(Layout::Spaced);
+            }
+            let message = self.to_string();
+            if inner.is_none() && message == kind.to_string() {
+                return Expr::path("::std::io::Error::from").call([kind.to_build_expr()]);
+            }
+            Expr::path("::std::io::Error::new")
+                .call([kind.to_build_expr(), message.as_str().to_build_expr()])
+                .with_layout(Layout::Spaced)
+        })
+    }
+}
+
+impl BuildStr for SeekFrom {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            match self {
+                SeekFrom::Start(n) => Expr::path("::std::io::SeekFrom::Start").call([n.to_build_expr()]),
+                SeekFrom::End(n) => Expr::path("::std::io::SeekFrom::End").call([n.to_build_expr()]),
+                SeekFrom::Current(n) => {
+                    Expr::path("::std::io::SeekFrom::Current").call([n.to_build_expr()])
+                }
+            }
+        })
+    }
+}
+
+impl BuildStr for Empty {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::path("::std::io::empty").call([]))
+    }
+}
+
+impl BuildStr for Sink {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::path("::std::io::sink").call([]))
+    }
+}
+
+impl BuildStr for Stdin {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::path("::std::io::stdin").call([]))
+    }
+}
+
+impl BuildStr for Stdout {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::path("::std::io::stdout").call([]))
+    }
+}
+
+impl BuildStr for Stderr {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::path("::std::io::stderr").call([]))
+    }
+}
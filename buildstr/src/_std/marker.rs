@@ -1,7 +1,9 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for ::core::marker::PhantomPinned {
-    fn to_build_string(&self) -> String {
-        "::core::marker::PhantomPinned".into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::marker::PhantomPinned")
+        })
     }
 }
\ No newline at end of file
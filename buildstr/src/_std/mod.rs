@@ -33,13 +33,16 @@ impls! {
         error,
         ffi,
         fmt,
+        io,
         marker,
         net,
         num,
         ops,
         path,
         process,
+        rc,
         str,
+        sync,
         time
     ]
 }
\ No newline at end of file
@@ -1,51 +1,89 @@
-use crate::BuildStr;
+use crate::__private::fail;
+use crate::{BuildStr, Expr, Layout, RenderError};
 
 impl BuildStr for ::std::net::IpAddr {
-    fn to_build_string(&self) -> String {
-        match self {
-            ::std::net::IpAddr::V4(v) => format!("::std::net::IpAddr::V4({})", v.to_build_string()),
-            ::std::net::IpAddr::V6(v) => format!("::std::net::IpAddr::V6({})", v.to_build_string()),
-        }
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            match self {
+                ::std::net::IpAddr::V4(v) => Expr::path("::std::net::IpAddr::V4").call([v.to_build_expr()]),
+                ::std::net::IpAddr::V6(v) => Expr::path("::std::net::IpAddr::V6").call([v.to_build_expr()]),
+            }
+        })
     }
 }
 impl BuildStr for ::std::net::Ipv4Addr {
-    fn to_build_string(&self) -> String {
-        format!("::std::net::Ipv4Addr::from({})", self.octets().to_build_string())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::net::Ipv4Addr::from").call([self.octets().to_build_expr()])
+        })
     }
 }
 impl BuildStr for ::std::net::Ipv6Addr {
-    fn to_build_string(&self) -> String {
-        format!("::std::net::Ipv6Addr::from({})", self.octets().to_build_string())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::net::Ipv6Addr::from").call([self.octets().to_build_expr()])
+        })
     }
 }
 impl BuildStr for ::std::net::Shutdown {
-    fn to_build_string(&self) -> String {
-        match self {
-            ::std::net::Shutdown::Read => "::std::net::Shutdown::Read",
-            ::std::net::Shutdown::Write => "::std::net::Shutdown::Write",
-            ::std::net::Shutdown::Both => "::std::net::Shutdown::Both",
-        }.into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path(match self {
+                ::std::net::Shutdown::Read => "::std::net::Shutdown::Read",
+                ::std::net::Shutdown::Write => "::std::net::Shutdown::Write",
+                ::std::net::Shutdown::Both => "::std::net::Shutdown::Both",
+            })
+        })
     }
 }
 impl BuildStr for ::std::net::SocketAddr {
-    fn to_build_string(&self) -> String {
-        match self {
-            ::std::net::SocketAddr::V4(v) => format!("::std::net::SocketAddr::V4({})", v.to_build_string()),
-            ::std::net::SocketAddr::V6(v) => format!("::std::net::SocketAddr::V6({})", v.to_build_string())
-        }
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            match self {
+                ::std::net::SocketAddr::V4(v) => Expr::path("::std::net::SocketAddr::V4").call([v.to_build_expr()]),
+                ::std::net::SocketAddr::V6(v) => Expr::path("::std::net::SocketAddr::V6").call([v.to_build_expr()])
+            }
+        })
     }
 }
 impl BuildStr for ::std::net::SocketAddrV4 {
-    fn to_build_string(&self) -> String {
-        format!("::std::net::SocketAddrV4::new({}, {})", self.ip().to_build_string(), self.port())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::net::SocketAddrV4::new")
+                .call([self.ip().to_build_expr(), Expr::Lit(self.port().to_string())])
+                .with_layout(Layout::Spaced)
+        })
     }
 }
 impl BuildStr for ::std::net::SocketAddrV6 {
-    fn to_build_string(&self) -> String {
-        let ip = self.ip().to_build_string();
-        let port = self.port();
-        let flowinfo = self.flowinfo();
-        let scope_id = self.scope_id();
-        format!("::std::net::SocketAddrV6::new({ip}, {port}, {flowinfo}, {scope_id})")
-    }
-}
\ No newline at end of file
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let ip = self.ip().to_build_expr();
+            let port = Expr::Lit(self.port().to_string());
+            let flowinfo = Expr::Lit(self.flowinfo().to_string());
+            let scope_id = Expr::Lit(self.scope_id().to_string());
+            Expr::path("::std::net::SocketAddrV6::new")
+                .call([ip, port, flowinfo, scope_id])
+                .with_layout(Layout::Spaced)
+        })
+    }
+}
+impl BuildStr for ::std::net::AddrParseError {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = self.to_string();
+            let ty = match s.as_str() {
+                "invalid IP address syntax" => "::std::net::IpAddr",
+                "invalid IPv4 address syntax" => "::std::net::Ipv4Addr",
+                "invalid IPv6 address syntax" => "::std::net::Ipv6Addr",
+                "invalid socket address syntax" => "::std::net::SocketAddr",
+                "invalid IPv4 socket address syntax" => "::std::net::SocketAddrV4",
+                "invalid IPv6 socket address syntax" => "::std::net::SocketAddrV6",
+                _ => return fail::<Self>(|path| RenderError::Unrecognized { path }),
+            };
+            Expr::path(&format!("<{ty} as ::std::str::FromStr>::from_str"))
+                .call([Expr::Lit("\"\"".into())])
+                .method("unwrap_err", [])
+        })
+    }
+}
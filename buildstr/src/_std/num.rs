@@ -1,91 +1,156 @@
-use crate::BuildStr;
+use crate::__private::fail;
+use crate::{BuildStr, Expr, RenderError};
 
 impl BuildStr for ::core::num::FpCategory {
-    fn to_build_string(&self) -> String {
-        match self {
-            ::core::num::FpCategory::Nan => "::core::num::FpCategory::Nan",
-            ::core::num::FpCategory::Infinite => "::core::num::FpCategory::Infinite",
-            ::core::num::FpCategory::Zero => "::core::num::FpCategory::Zero",
-            ::core::num::FpCategory::Subnormal => "::core::num::FpCategory::Subnormal",
-            ::core::num::FpCategory::Normal => "::core::num::FpCategory::Normal",
-        }.into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path(match self {
+                ::core::num::FpCategory::Nan => "::core::num::FpCategory::Nan",
+                ::core::num::FpCategory::Infinite => "::core::num::FpCategory::Infinite",
+                ::core::num::FpCategory::Zero => "::core::num::FpCategory::Zero",
+                ::core::num::FpCategory::Subnormal => "::core::num::FpCategory::Subnormal",
+                ::core::num::FpCategory::Normal => "::core::num::FpCategory::Normal",
+            })
+        })
     }
 }
 impl BuildStr for ::core::num::IntErrorKind {
-    fn to_build_string(&self) -> String {
-        match self {
-            ::core::num::IntErrorKind::Empty => "::core::num::IntErrorKind::Empty",
-            ::core::num::IntErrorKind::InvalidDigit => "::core::num::IntErrorKind::InvalidDigit",
-            ::core::num::IntErrorKind::PosOverflow => "::core::num::IntErrorKind::PosOverflow",
-            ::core::num::IntErrorKind::NegOverflow => "::core::num::IntErrorKind::NegOverflow",
-            ::core::num::IntErrorKind::Zero => "::core::num::IntErrorKind::Zero",
-            _ => unreachable!("IntErrorKind should not have another value"),
-        }.into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path(match self {
+                ::core::num::IntErrorKind::Empty => "::core::num::IntErrorKind::Empty",
+                ::core::num::IntErrorKind::InvalidDigit => "::core::num::IntErrorKind::InvalidDigit",
+                ::core::num::IntErrorKind::PosOverflow => "::core::num::IntErrorKind::PosOverflow",
+                ::core::num::IntErrorKind::NegOverflow => "::core::num::IntErrorKind::NegOverflow",
+                ::core::num::IntErrorKind::Zero => "::core::num::IntErrorKind::Zero",
+                _ => unreachable!("IntErrorKind should not have another value"),
+            })
+        })
     }
 }
 impl BuildStr for ::core::num::ParseIntError {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::ParseIntError::new({})", self.kind().to_build_string())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let (ty, s) = match self.kind() {
+                ::core::num::IntErrorKind::Empty => ("::std::primitive::u8", ""),
+                ::core::num::IntErrorKind::InvalidDigit => ("::std::primitive::u8", "a"),
+                ::core::num::IntErrorKind::PosOverflow => ("::std::primitive::u8", "256"),
+                ::core::num::IntErrorKind::NegOverflow => ("::std::primitive::i8", "-129"),
+                ::core::num::IntErrorKind::Zero => ("::core::num::NonZeroU8", "0"),
+                _ => return fail::<Self>(|path| RenderError::Unrecognized { path }),
+            };
+            Expr::path(&format!("<{ty} as ::std::str::FromStr>::from_str"))
+                .call([Expr::Lit(format!("{s:?}"))])
+                .method("unwrap_err", [])
+        })
+    }
+}
+impl BuildStr for ::core::num::ParseFloatError {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = self.to_string();
+            let s = match s.as_str() {
+                "cannot parse float from empty string" => "\"\"",
+                "invalid float literal" => "\"a\"",
+                _ => return fail::<Self>(|path| RenderError::Unrecognized { path }),
+            };
+            Expr::path("<::std::primitive::f64 as ::std::str::FromStr>::from_str")
+                .call([Expr::Lit(s.into())])
+                .method("unwrap_err", [])
+        })
+    }
+}
+impl BuildStr for ::core::num::TryFromIntError {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("<::std::primitive::u8 as ::core::convert::TryFrom<::std::primitive::i8>>::try_from")
+                .call([crate::__private::integer(true, 1, "i8")])
+                .method("unwrap_err", [])
+        })
     }
 }
 
 impl BuildStr for ::core::num::NonZeroU8 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroU8::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroU8::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroU16 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroU16::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroU16::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroU32 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroU32::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroU32::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroU64 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroU64::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroU64::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroU128 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroU128::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroU128::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroUsize {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroUsize::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroUsize::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroI8 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroI8::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroI8::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroI16 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroI16::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroI16::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroI32 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroI32::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroI32::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroI64 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroI64::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroI64::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroI128 {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroI128::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroI128::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
 impl BuildStr for ::core::num::NonZeroIsize {
-    fn to_build_string(&self) -> String {
-        format!("::core::num::NonZeroIsize::new({})", self.get())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::core::num::NonZeroIsize::new").call([Expr::Lit(self.get().to_string())])
+        })
     }
 }
\ No newline at end of file
@@ -1,7 +1,13 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for ::core::ops::RangeFull {
-    fn to_build_string(&self) -> String {
-        "::core::ops::RangeFull".into()
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            if crate::__private::idiomatic() {
+                Expr::Range { start: None, end: None, inclusive: false }
+            } else {
+                Expr::path("::core::ops::RangeFull")
+            }
+        })
     }
 }
\ No newline at end of file
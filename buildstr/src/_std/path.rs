@@ -1,12 +1,70 @@
-use buildstr::BuildStr;
+use crate::{BuildStr, Expr, Stmt};
+
+use super::ffi::os_str;
 
 impl BuildStr for &::std::path::Path {
-    fn to_build_string(&self) -> String {
-        format!("::std::path::Path::new({self:?})")
+    fn to_build_expr(&self) -> Expr {
+        Expr::path("::std::path::Path::new").call([os_str(self.as_os_str())])
     }
 }
+
 impl BuildStr for ::std::path::PathBuf {
-    fn to_build_string(&self) -> String {
-        format!("::std::path::PathBuf::from({self:?})")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::path::PathBuf::from").call([os_str(self.as_os_str())])
+        })
+    }
+}
+
+impl BuildStr for ::std::path::Component<'_> {
+    fn to_build_expr(&self) -> Expr {
+        use std::path::Component;
+
+        match self {
+            // Prefixes can only be obtained by parsing a path
+            Component::Prefix(prefix) => Expr::path("::std::path::Path::new")
+                .call([os_str(prefix.as_os_str())])
+                .method("components", [])
+                .method("next", [])
+                .method("unwrap", []),
+            Component::RootDir => Expr::path("::std::path::Component::RootDir"),
+            Component::CurDir => Expr::path("::std::path::Component::CurDir"),
+            Component::ParentDir => Expr::path("::std::path::Component::ParentDir"),
+            Component::Normal(s) => {
+                Expr::path("::std::path::Component::Normal").call([s.to_build_expr()])
+            }
+        }
+    }
+}
+
+impl BuildStr for ::std::path::Display<'_> {
+    fn to_build_expr(&self) -> Expr {
+        // The path can't be read back, but a lossy one displays the same text
+        Expr::path("::std::path::Path::new")
+            .call([crate::__private::string(&self.to_string())])
+            .method("display", [])
+    }
+}
+
+impl BuildStr for ::std::path::Ancestors<'_> {
+    fn to_build_expr(&self) -> Expr {
+        if let Some(path) = self.clone().next() {
+            return path.to_build_expr().method("ancestors", []);
+        }
+        let ancestors = Expr::path("::std::path::Path::new")
+            .call([Expr::Lit("\"\"".into())])
+            .method("ancestors", []);
+        Expr::block(
+            vec![
+                Stmt::Let {
+                    mutable: true,
+                    name: "ancestors".into(),
+                    ty: None,
+                    init: ancestors,
+                },
+                Stmt::Expr(Expr::path("ancestors").method("next", [])),
+            ],
+            Expr::path("ancestors"),
+        )
     }
 }
\ No newline at end of file
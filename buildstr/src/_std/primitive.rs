@@ -1,92 +1,119 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for bool {
-    fn to_build_string(&self) -> String {
-        format!("{self:?}")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::Lit(format!("{self:?}")))
     }
 }
 impl BuildStr for char {
-    fn to_build_string(&self) -> String {
-        format!("{self:?}")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::Lit(format!("{self:?}")))
     }
 }
 impl BuildStr for &str {
-    fn to_build_string(&self) -> String {
-        format!("\"{self}\"")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::include_string(self).unwrap_or_else(|| Expr::Lit(format!("\"{self}\"")))
     }
 }
 impl BuildStr for u8 {
-    fn to_build_string(&self) -> String {
-        format!("{self}u8")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(false, u128::from(*self), "u8")
+        })
+    }
+    fn __bytes(slice: &[Self]) -> Option<&[u8]> {
+        Some(slice)
     }
 }
 impl BuildStr for u16 {
-    fn to_build_string(&self) -> String {
-        format!("{self}u16")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(false, u128::from(*self), "u16")
+        })
     }
 }
 impl BuildStr for u32 {
-    fn to_build_string(&self) -> String {
-        format!("{self}u32")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(false, u128::from(*self), "u32")
+        })
     }
 }
 impl BuildStr for u64 {
-    fn to_build_string(&self) -> String {
-        format!("{self}u64")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(false, u128::from(*self), "u64")
+        })
     }
 }
 impl BuildStr for u128 {
-    fn to_build_string(&self) -> String {
-        format!("{self}u128")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(false, *self, "u128")
+        })
     }
 }
 impl BuildStr for usize {
-    fn to_build_string(&self) -> String {
-        format!("{self}usize")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(false, *self as u128, "usize")
+        })
     }
 }
 impl BuildStr for i8 {
-    fn to_build_string(&self) -> String {
-        format!("{self}i8")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(*self < 0, u128::from(self.unsigned_abs()), "i8")
+        })
     }
 }
 impl BuildStr for i16 {
-    fn to_build_string(&self) -> String {
-        format!("{self}i16")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(*self < 0, u128::from(self.unsigned_abs()), "i16")
+        })
     }
 }
 impl BuildStr for i32 {
-    fn to_build_string(&self) -> String {
-        format!("{self}i32")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(*self < 0, u128::from(self.unsigned_abs()), "i32")
+        })
     }
 }
 impl BuildStr for i64 {
-    fn to_build_string(&self) -> String {
-        format!("{self}i64")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(*self < 0, u128::from(self.unsigned_abs()), "i64")
+        })
     }
 }
 impl BuildStr for i128 {
-    fn to_build_string(&self) -> String {
-        format!("{self}i128")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(*self < 0, self.unsigned_abs(), "i128")
+        })
     }
 }
 impl BuildStr for isize {
-    fn to_build_string(&self) -> String {
-        format!("{self}isize")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            crate::__private::integer(*self < 0, self.unsigned_abs() as u128, "isize")
+        })
     }
 }
 impl BuildStr for f32 {
-    fn to_build_string(&self) -> String {
-        format!("{self}f32")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || crate::__private::float(*self, "f32"))
     }
 }
 impl BuildStr for f64 {
-    fn to_build_string(&self) -> String {
-        format!("{self}f64")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || crate::__private::float(*self, "f64"))
     }
 }
 impl BuildStr for () {
-    fn to_build_string(&self) -> String {
-        ::std::string::String::from("()")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::tuple([]))
     }
 }
\ No newline at end of file
@@ -1,28 +1,160 @@
 use buildstr::BuildStr;
 
+use super::ffi::os_str;
+use crate::__private::fail;
+use crate::{Expr, Layout, Path, RenderError, Stmt};
+
+/// Reads whether the environment is cleared from the alternate `Debug` of a `Command`,
+/// as `get_envs` doesn't reflect it.
+///
+/// It's best-effort, as the layout of the `Debug` isn't stable: the environment is omitted while it's
+/// the default, and `None` is returned if the `Debug` isn't written in a recognized way.
+fn env_clear(debug: &str, has_envs: bool) -> Option<bool> {
+    if !debug.starts_with("Command {\n    program: ") {
+        return None;
+    }
+    let Some((_, rest)) = debug.split_once("\n    env: CommandEnv {\n") else {
+        return (!has_envs).then_some(false);
+    };
+    let rest = rest.trim_start().strip_prefix("clear: ")?;
+    if rest.starts_with("true,") {
+        Some(true)
+    } else if rest.starts_with("false,") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Reads the variant of the stdio `name` (`stdin`, `stdout` or `stderr`) from the alternate `Debug`
+/// of a `Command`, the only way to get its configuration that has no getter.
+///
+/// Like [`env_clear`], it's best-effort: the stdio is omitted while it's the default,
+/// and `Err` is returned if it isn't written in a recognized way.
+fn stdio<'a>(debug: &'a str, name: &str) -> Result<Option<&'a str>, ()> {
+    let Some((_, rest)) = debug.split_once(&format!("\n    {name}: ")) else {
+        return Ok(None);
+    };
+    rest.strip_prefix("Some(\n")
+        .and_then(|rest| {
+            rest.trim_start()
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .next()
+        })
+        .filter(|variant| !variant.is_empty())
+        .map(Some)
+        .ok_or(())
+}
+
 impl BuildStr for ::std::process::Command {
-    fn to_build_string(&self) -> String {
-        let program = self.get_program().to_build_string();
-        let args = self.get_args().collect::<Vec<_>>().to_build_string();
-        let mut envs = self.get_envs().collect::<Vec<_>>();
-        let mut removed = Vec::new();
-        for i in 0..envs.len() {
-            if envs[i].1.is_none() {
-                removed.push(envs.remove(i).0);
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let debug = format!("{self:#?}");
+            let new = Expr::path("::std::process::Command::new").call([os_str(self.get_program())]);
+            let mut stmts = vec![Stmt::Let {
+                mutable: true,
+                name: "command".into(),
+                ty: None,
+                init: new,
+            }];
+            let mut call = |name: &str, args: Vec<Expr>| {
+                stmts.push(Stmt::Expr(Expr::path("command").method(name, args)));
+            };
+            // The arguments that aren't UTF-8 are `&OsStr`, which can't be in the same array as `&str`
+            for arg in self.get_args() {
+                call("arg", vec![os_str(arg)]);
+            }
+            // The removed variables are dropped when it's cleared
+            match env_clear(&debug, self.get_envs().next().is_some()) {
+                None => return fail::<Self>(|path| RenderError::Unrecognized { path }),
+                Some(true) => call("env_clear", vec![]),
+                Some(false) => {}
+            }
+            for (key, value) in self.get_envs() {
+                match value {
+                    Some(value) => call("env", vec![os_str(key), os_str(value)]),
+                    None => call("env_remove", vec![os_str(key)]),
+                }
+            }
+            if let Some(dir) = self.get_current_dir() {
+                call("current_dir", vec![os_str(dir.as_os_str())]);
+            }
+            for name in ["stdin", "stdout", "stderr"] {
+                let stdio = match stdio(&debug, name) {
+                    Ok(None) => continue,
+                    Ok(Some("Inherit")) => "inherit",
+                    Ok(Some("Null")) => "null",
+                    Ok(Some("MakePipe")) => "piped",
+                    Ok(Some("Fd" | "StaticFd" | "Pipe" | "Handle" | "InheritSpecific")) => {
+                        return fail::<Self>(|path| RenderError::Redirected { path });
+                    }
+                    Ok(Some(_)) | Err(()) => {
+                        return fail::<Self>(|path| RenderError::Unrecognized { path });
+                    }
+                };
+                call(name, vec![Expr::path(&format!("::std::process::Stdio::{stdio}")).call([])]);
+            }
+            Expr::block(stmts, Expr::path("command"))
+        })
+    }
+}
+
+#[cfg(unix)]
+impl BuildStr for ::std::process::ExitStatus {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            use std::os::unix::process::ExitStatusExt;
+
+            Expr::path("<::std::process::ExitStatus as ::std::os::unix::process::ExitStatusExt>::from_raw")
+                .call([self.into_raw().to_build_expr()])
+        })
+    }
+}
+
+#[cfg(windows)]
+impl BuildStr for ::std::process::ExitStatus {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            // The raw code is the same as the one of the status
+            let code = self.code().unwrap_or_default().cast_unsigned();
+            Expr::path("<::std::process::ExitStatus as ::std::os::windows::process::ExitStatusExt>::from_raw")
+                .call([code.to_build_expr()])
+        })
+    }
+}
+
+impl BuildStr for ::std::process::ExitCode {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            // The code has no getter, but it's the only number in the `Debug`, like `ExitCode(unix_exit_status(1))`
+            let debug = format!("{self:?}");
+            // so it's best-effort and fails if the `Debug` has no number
+            let code = debug
+                .split(|c: char| !c.is_ascii_digit())
+                .find(|s| !s.is_empty())
+                .and_then(|s| s.parse::<u8>().ok());
+            match code {
+                Some(0) => Expr::path("::std::process::ExitCode::SUCCESS"),
+                Some(1) => Expr::path("::std::process::ExitCode::FAILURE"),
+                Some(code) => Expr::path("::std::process::ExitCode::from").call([code.to_build_expr()]),
+                None => fail::<Self>(|path| RenderError::Unrecognized { path }),
+            }
+        })
+    }
+}
+
+impl BuildStr for ::std::process::Output {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::StructLit {
+                path: Path::parse("::std::process::Output"),
+                fields: vec![
+                    ("status".into(), self.status.to_build_expr()),
+                    ("stdout".into(), self.stdout.to_build_expr()),
+                    ("stderr".into(), self.stderr.to_build_expr()),
+                ],
+                layout: Layout::Terminated,
             }
-        }
-        let envs = envs.into_iter().map(|(k, v)| (k, v.unwrap())).collect::<Vec<_>>().to_build_string();
-        let dir = self.get_current_dir();
-        let mut s = format!("{{::std::process::Command::new({program})");
-        s = format!("{s}.args({args}).envs({envs})");
-        if let Some(dir) = dir {
-            s = format!("{s}.current_dir({})", dir.to_build_string());
-        }
-        for k in removed {
-            s = format!("{s}.env_remove({})", k.to_build_string());
-        }
-        s.push('}');
-        s
-        // let command = Self::new(program).args(args).envs(envs).current_dir(dir);
+        })
     }
 }
\ No newline at end of file
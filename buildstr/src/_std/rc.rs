@@ -0,0 +1,38 @@
+use std::rc::Rc;
+
+use crate::{BuildStr, Expr};
+
+impl BuildStr for Rc<str> {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let name = std::any::type_name::<Self>();
+            crate::__private::shared_from(Rc::as_ptr(self), name, "::std::rc::Rc", "str", || {
+                (&**self).to_build_expr()
+            })
+        })
+    }
+}
+
+impl BuildStr for Rc<::std::ffi::OsStr> {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let name = std::any::type_name::<Self>();
+            let ty = "::std::ffi::OsStr";
+            crate::__private::shared_from(Rc::as_ptr(self), name, "::std::rc::Rc", ty, || {
+                (&**self).to_build_expr()
+            })
+        })
+    }
+}
+
+impl BuildStr for Rc<::std::path::Path> {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let name = std::any::type_name::<Self>();
+            let ty = "::std::path::Path";
+            crate::__private::shared_from(Rc::as_ptr(self), name, "::std::rc::Rc", ty, || {
+                (&**self).to_build_expr()
+            })
+        })
+    }
+}
@@ -1 +1,41 @@
+use crate::__private::fail;
+use crate::{BuildStr, Expr, RenderError};
 
+impl BuildStr for ::core::str::ParseBoolError {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("<::std::primitive::bool as ::std::str::FromStr>::from_str")
+                .call([Expr::Lit("\"\"".into())])
+                .method("unwrap_err", [])
+        })
+    }
+}
+
+impl BuildStr for ::core::str::Utf8Error {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            // The valid bytes are followed by a sequence of the same length as the invalid one,
+            // or by the start of a sequence that is cut short
+            let invalid = match self.error_len() {
+                None => "\\xe2",
+                Some(1) => "\\xff",
+                Some(2) => "\\xe2\\x82(",
+                Some(3) => "\\xf0\\x90\\x80(",
+                Some(_) => return fail::<Self>(|path| RenderError::Unrecognized { path }),
+            };
+            let valid = self.valid_up_to();
+            let bytes = if valid <= 64 {
+                let lit = format!("b\"{}{invalid}\"", "a".repeat(valid));
+                Expr::path("::std::vec::Vec::from").call([Expr::Lit(lit)])
+            } else {
+                // Slicing has no expression, so the long prefix is written as is
+                Expr::Verbatim(format!("[&[b'a'; {valid}][..], b\"{invalid}\"].concat()"))
+            };
+            // `String::from_utf8` is used because `str::from_utf8` warns about invalid literals
+            Expr::path("::std::string::String::from_utf8")
+                .call([bytes])
+                .method("unwrap_err", [])
+                .method("utf8_error", [])
+        })
+    }
+}
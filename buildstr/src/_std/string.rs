@@ -1,7 +1,24 @@
-use crate::BuildStr;
+use crate::{BuildStr, Expr};
 
 impl BuildStr for ::std::string::String {
-    fn to_build_string(&self) -> String {
-        format!("::std::string::String::from({self:?})")
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = crate::__private::string(self);
+            if crate::__private::idiomatic() {
+                Expr::path("String::from").call([s])
+            } else {
+                Expr::path("::std::string::String::from").call([s])
+            }
+        })
     }
-}
\ No newline at end of file
+}
+
+impl BuildStr for ::std::string::FromUtf8Error {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::string::String::from_utf8")
+                .call([self.as_bytes().to_vec().to_build_expr()])
+                .method("unwrap_err", [])
+        })
+    }
+}
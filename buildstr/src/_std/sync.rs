@@ -0,0 +1,103 @@
+mod atomic;
+
+use std::sync::Arc;
+
+use crate::{BuildStr, Expr, Stmt};
+
+impl BuildStr for Arc<str> {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let name = std::any::type_name::<Self>();
+            crate::__private::shared_from(Arc::as_ptr(self), name, "::std::sync::Arc", "str", || {
+                (&**self).to_build_expr()
+            })
+        })
+    }
+}
+
+impl BuildStr for Arc<::std::ffi::OsStr> {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let name = std::any::type_name::<Self>();
+            let ty = "::std::ffi::OsStr";
+            crate::__private::shared_from(Arc::as_ptr(self), name, "::std::sync::Arc", ty, || {
+                (&**self).to_build_expr()
+            })
+        })
+    }
+}
+
+impl BuildStr for Arc<::std::path::Path> {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let name = std::any::type_name::<Self>();
+            let ty = "::std::path::Path";
+            crate::__private::shared_from(Arc::as_ptr(self), name, "::std::sync::Arc", ty, || {
+                (&**self).to_build_expr()
+            })
+        })
+    }
+}
+
+impl BuildStr for ::std::sync::Condvar {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path("::std::sync::Condvar::new").call([])
+        })
+    }
+}
+
+impl BuildStr for ::std::sync::Once {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let new = Expr::path("::std::sync::Once::new").call([]);
+            if self.is_completed() {
+                let callback = Expr::Closure {
+                    params: Vec::new(),
+                    body: Box::new(Expr::Block {
+                        unsafety: false,
+                        stmts: Vec::new(),
+                        expr: None,
+                    }),
+                };
+                Expr::block(
+                    vec![
+                        Stmt::Let { mutable: false, name: "once".into(), ty: None, init: new },
+                        Stmt::Expr(Expr::path("once").method("call_once", [callback])),
+                    ],
+                    Expr::path("once"),
+                )
+            } else {
+                new
+            }
+        })
+    }
+}
+
+impl BuildStr for ::std::sync::mpsc::RecvError {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || Expr::path("::std::sync::mpsc::RecvError"))
+    }
+}
+
+impl BuildStr for ::std::sync::mpsc::TryRecvError {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path(match self {
+                Self::Empty => "::std::sync::mpsc::TryRecvError::Empty",
+                Self::Disconnected => "::std::sync::mpsc::TryRecvError::Disconnected",
+            })
+        })
+    }
+}
+
+impl BuildStr for ::std::sync::mpsc::RecvTimeoutError {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            Expr::path(match self {
+                Self::Timeout => "::std::sync::mpsc::RecvTimeoutError::Timeout",
+                Self::Disconnected => "::std::sync::mpsc::RecvTimeoutError::Disconnected",
+            })
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::atomic::Ordering;
+
+use crate::{BuildStr, Expr};
+
+/// Gets the ordering used to load atomics, see [`Options::atomic_ordering`](crate::Options::atomic_ordering).
+///
+/// `Release` and `AcqRel` only apply to stores, so they load with `Relaxed` and `Acquire`.
+fn ordering() -> Ordering {
+    match crate::__private::context::with(|o| o.atomic_ordering) {
+        Ordering::Release => Ordering::Relaxed,
+        Ordering::AcqRel => Ordering::Acquire,
+        ordering => ordering,
+    }
+}
+
+macro_rules! atomics {
+    ($($atomic:ident),*) => {
+        $(
+            impl BuildStr for ::std::sync::atomic::$atomic {
+                fn to_build_expr(&self) -> Expr {
+                    crate::__private::render_static::<Self>(self, || {
+                        Expr::path(concat!("::core::sync::atomic::", stringify!($atomic), "::new"))
+                            .call([self.load(ordering()).to_build_expr()])
+                    })
+                }
+            }
+        )*
+    };
+}
+
+atomics!(
+    AtomicBool,
+    AtomicU8,
+    AtomicU16,
+    AtomicU32,
+    AtomicU64,
+    AtomicUsize,
+    AtomicI8,
+    AtomicI16,
+    AtomicI32,
+    AtomicI64,
+    AtomicIsize
+);
+
+impl BuildStr for Ordering {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            // `Ordering` is non-exhaustive, so its variants are written with their `Debug` names
+            Expr::path(&format!("::core::sync::atomic::Ordering::{self:?}"))
+        })
+    }
+}
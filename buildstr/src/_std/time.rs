@@ -1,7 +1,86 @@
-use crate::BuildStr;
+use std::time::{Instant, SystemTime, SystemTimeError, UNIX_EPOCH};
+
+use crate::__private::{context, track};
+use crate::{BuildStr, Expr, Instants, Layout, RenderError};
 
 impl BuildStr for ::core::time::Duration {
-    fn to_build_string(&self) -> String {
-        format!("::core::time::Duration::new({}, {})", self.as_secs(), self.subsec_nanos())
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let secs = Expr::Lit(self.as_secs().to_string());
+            let nanos = Expr::Lit(self.subsec_nanos().to_string());
+            Expr::path("::core::time::Duration::new")
+                .call([secs, nanos])
+                .with_layout(Layout::Spaced)
+        })
+    }
+}
+
+/// Offsets the time `base` by `duration`, like `::std::time::UNIX_EPOCH + duration`.
+fn offset(base: Expr, op: char, duration: ::core::time::Duration) -> Expr {
+    Expr::Binary {
+        lhs: Box::new(base),
+        op,
+        rhs: Box::new(duration.to_build_expr()),
+    }
+}
+
+impl BuildStr for SystemTime {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let epoch = Expr::path("::std::time::UNIX_EPOCH");
+            match self.duration_since(UNIX_EPOCH) {
+                Ok(after) => offset(epoch, '+', after),
+                Err(before) => offset(epoch, '-', before.duration()),
+            }
+        })
+    }
+}
+
+impl BuildStr for Instant {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            if context::with(|o| o.instants) == Instants::Fail {
+                let mut path = track::current();
+                path.push(std::any::type_name::<Self>());
+                context::fail(RenderError::Instant { path });
+                return Expr::Verbatim(String::new());
+            }
+            let now = context::now();
+            let base = Expr::path("::std::time::Instant::now").call([]);
+            match now.checked_duration_since(*self) {
+                Some(before) => offset(base, '-', before),
+                None => offset(base, '+', self.duration_since(now)),
+            }
+        })
+    }
+}
+
+impl BuildStr for SystemTimeError {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let later = offset(Expr::path("::std::time::UNIX_EPOCH"), '+', self.duration());
+            Expr::path("::std::time::UNIX_EPOCH")
+                .method("duration_since", [later])
+                .method("unwrap_err", [])
+        })
+    }
+}
+
+impl BuildStr for ::core::time::TryFromFloatSecsError {
+    fn to_build_expr(&self) -> Expr {
+        crate::__private::render_static::<Self>(self, || {
+            let s = self.to_string();
+            let secs = if s.ends_with("negative") {
+                Expr::Unary {
+                    op: '-',
+                    expr: Box::new(Expr::Lit("1.0".into())),
+                }
+            } else {
+                Expr::path("::core::primitive::f64::NAN")
+            };
+            Expr::path("::core::time::Duration::try_from_secs_f64")
+                .call([secs])
+                .method("unwrap_err", [])
+        })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,118 @@
+//! Helpers for build scripts, writing the builder of a value to a file in `OUT_DIR`.
+//!
+//! The file can then be included in the crate with
+//! `include!(concat!(env!("OUT_DIR"), "/name.rs"))`.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{BuildStr, Expr, Options};
+
+/// Writes the builder of `value` to the file `name` in `OUT_DIR`, returning its path.
+///
+/// See [`write_with`].
+///
+/// # Errors
+/// Returns an error if `OUT_DIR` isn't set, the value can't be rendered, or a file can't be written.
+pub fn write<T: BuildStr + ?Sized>(value: &T, name: &str) -> io::Result<PathBuf> {
+    write_with(value, name, &Options::default())
+}
+
+/// Writes the builder of `value`, rendered with the specified [`Options`], to the file `name` in `OUT_DIR`,
+/// returning its path.
+///
+/// Byte sequences and strings of at least [`Options::externalize`] bytes are written to sidecar files next to it,
+/// named `{stem}_blob_{N}.bin` and `{stem}_blob_{N}.txt` (where `stem` is `name` without its extension),
+/// and included with `include_bytes!` or `include_str!`.
+///
+/// # Examples
+/// ```no_run
+/// // build.rs
+/// fn main() -> std::io::Result<()> {
+///     let table: Vec<u8> = (0..=255).map(|b: u8| b.reverse_bits()).collect();
+///     buildstr::build::write(&table, "table.rs")?;
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+/// Returns an error if `OUT_DIR` isn't set, the value can't be rendered, or a file can't be written.
+pub fn write_with<T: BuildStr + ?Sized>(
+    value: &T,
+    name: &str,
+    options: &Options,
+) -> io::Result<PathBuf> {
+    write_expr(name, options, || value.to_build_expr())
+}
+
+/// Writes a `LazyLock` that rebuilds `value` from its compressed bytes to the file `name` in `OUT_DIR`,
+/// returning its path.
+///
+/// See [`write_compressed_with`].
+///
+/// # Errors
+/// Returns an error if `OUT_DIR` isn't set or a file can't be written.
+#[cfg(feature = "compress")]
+pub fn write_compressed<T: crate::FromBuildBytes>(value: &T, name: &str) -> io::Result<PathBuf> {
+    write_compressed_with(value, name, &Options::default())
+}
+
+/// Writes a `LazyLock` that rebuilds `value` from its compressed bytes to the file `name` in `OUT_DIR`,
+/// returning its path, see [`compress`](crate::compress).
+///
+/// If there are at least [`Options::externalize`] compressed bytes, they are written to a sidecar file,
+/// like in [`write_with`].
+///
+/// # Examples
+/// ```no_run
+/// // build.rs
+/// fn main() -> std::io::Result<()> {
+///     let words: Vec<String> = std::fs::read_to_string("words.txt")?.lines().map(String::from).collect();
+///     buildstr::build::write_compressed(&words, "words.rs")?;
+///     Ok(())
+/// }
+///
+/// // lib.rs
+/// // static WORDS: std::sync::LazyLock<Vec<String>> = include!(concat!(env!("OUT_DIR"), "/words.rs"));
+/// ```
+///
+/// # Errors
+/// Returns an error if `OUT_DIR` isn't set or a file can't be written.
+#[cfg(feature = "compress")]
+pub fn write_compressed_with<T: crate::FromBuildBytes>(
+    value: &T,
+    name: &str,
+    options: &Options,
+) -> io::Result<PathBuf> {
+    write_expr(name, options, || crate::compress::lazy(value))
+}
+
+/// Writes the expression returned by `render` to the file `name` in `OUT_DIR`, with its sidecar files.
+fn write_expr(
+    name: &str,
+    options: &Options,
+    render: impl FnOnce() -> Expr,
+) -> io::Result<PathBuf> {
+    let dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "OUT_DIR is not set, only build scripts can write to it",
+        )
+    })?;
+    let dir = Path::new(&dir);
+    let stem = Path::new(name)
+        .file_stem()
+        .map_or_else(String::new, |s| s.to_string_lossy().into_owned());
+
+    let guard = crate::__private::context::enter(options.clone());
+    crate::__private::context::collect_blobs(format!("{stem}_"));
+    let expr = render();
+    let (expr, blobs) = guard.finish_with_blobs(expr).map_err(io::Error::other)?;
+
+    for (blob, data) in blobs {
+        std::fs::write(dir.join(blob), data)?;
+    }
+    let path = dir.join(name);
+    std::fs::write(&path, expr)?;
+    Ok(path)
+}
@@ -0,0 +1,326 @@
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::hash::{BuildHasher, Hash};
+
+use super::FromBuildBytes;
+
+/// Takes the first `n` bytes of `bytes`.
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Option<&'a [u8]> {
+    if bytes.len() < n {
+        return None;
+    }
+    let (head, tail) = bytes.split_at(n);
+    *bytes = tail;
+    Some(head)
+}
+
+/// Writes the length of a sequence.
+fn write_len(len: usize, out: &mut Vec<u8>) {
+    (len as u64).to_build_bytes(out);
+}
+
+/// Reads the length of a sequence.
+fn read_len(bytes: &mut &[u8]) -> Option<usize> {
+    usize::try_from(u64::from_build_bytes(bytes)?).ok()
+}
+
+/// Writes the elements of a sequence, after its length.
+fn write_seq<'a, T: FromBuildBytes + 'a>(
+    len: usize,
+    items: impl IntoIterator<Item = &'a T>,
+    out: &mut Vec<u8>,
+) {
+    write_len(len, out);
+    for item in items {
+        item.to_build_bytes(out);
+    }
+}
+
+/// Writes the entries of an unordered collection, sorted by their bytes so the output is the same across runs.
+fn write_unordered<I: IntoIterator>(
+    len: usize,
+    entries: I,
+    mut write: impl FnMut(I::Item, &mut Vec<u8>),
+    out: &mut Vec<u8>,
+) {
+    let mut entries = entries
+        .into_iter()
+        .map(|entry| {
+            let mut bytes = Vec::new();
+            write(entry, &mut bytes);
+            bytes
+        })
+        .collect::<Vec<_>>();
+    entries.sort_unstable();
+    write_len(len, out);
+    for entry in entries {
+        out.extend_from_slice(&entry);
+    }
+}
+
+/// Reads the elements of a sequence written by [`write_seq`].
+fn read_seq<T: FromBuildBytes, C: FromIterator<T>>(bytes: &mut &[u8]) -> Option<C> {
+    let len = read_len(bytes)?;
+    (0..len).map(|_| T::from_build_bytes(bytes)).collect()
+}
+
+macro_rules! numbers {
+    ($($ty:ty),*) => {
+        $(
+            impl FromBuildBytes for $ty {
+                fn to_build_bytes(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+                fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+                    let bytes = take(bytes, std::mem::size_of::<Self>())?;
+                    Some(Self::from_le_bytes(bytes.try_into().ok()?))
+                }
+            }
+        )*
+    };
+}
+
+numbers!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl FromBuildBytes for usize {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        (*self as u64).to_build_bytes(out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        Self::try_from(u64::from_build_bytes(bytes)?).ok()
+    }
+}
+
+impl FromBuildBytes for isize {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        (*self as i64).to_build_bytes(out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        Self::try_from(i64::from_build_bytes(bytes)?).ok()
+    }
+}
+
+impl FromBuildBytes for bool {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self));
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        match u8::from_build_bytes(bytes)? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl FromBuildBytes for char {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        u32::from(*self).to_build_bytes(out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        char::from_u32(u32::from_build_bytes(bytes)?)
+    }
+}
+
+impl FromBuildBytes for () {
+    fn to_build_bytes(&self, _: &mut Vec<u8>) {}
+    fn from_build_bytes(_: &mut &[u8]) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl FromBuildBytes for String {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        write_len(self.len(), out);
+        out.extend_from_slice(self.as_bytes());
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        let len = read_len(bytes)?;
+        let s = std::str::from_utf8(take(bytes, len)?).ok()?;
+        Some(s.to_owned())
+    }
+}
+
+impl<T: FromBuildBytes> FromBuildBytes for Box<T> {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        (**self).to_build_bytes(out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        T::from_build_bytes(bytes).map(Box::new)
+    }
+}
+
+impl<T: FromBuildBytes> FromBuildBytes for Option<T> {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        match self {
+            Some(value) => {
+                out.push(1);
+                value.to_build_bytes(out);
+            }
+            None => out.push(0),
+        }
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        match u8::from_build_bytes(bytes)? {
+            0 => Some(None),
+            1 => T::from_build_bytes(bytes).map(Some),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromBuildBytes, E: FromBuildBytes> FromBuildBytes for Result<T, E> {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        match self {
+            Ok(value) => {
+                out.push(0);
+                value.to_build_bytes(out);
+            }
+            Err(error) => {
+                out.push(1);
+                error.to_build_bytes(out);
+            }
+        }
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        match u8::from_build_bytes(bytes)? {
+            0 => T::from_build_bytes(bytes).map(Ok),
+            1 => E::from_build_bytes(bytes).map(Err),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromBuildBytes, const N: usize> FromBuildBytes for [T; N] {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        for item in self {
+            item.to_build_bytes(out);
+        }
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        let items = (0..N)
+            .map(|_| T::from_build_bytes(bytes))
+            .collect::<Option<Vec<_>>>()?;
+        items.try_into().ok()
+    }
+}
+
+impl<T: FromBuildBytes> FromBuildBytes for Vec<T> {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        write_seq(self.len(), self, out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        read_seq(bytes)
+    }
+}
+
+impl<T: FromBuildBytes> FromBuildBytes for VecDeque<T> {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        write_seq(self.len(), self, out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        read_seq(bytes)
+    }
+}
+
+impl<T: FromBuildBytes> FromBuildBytes for LinkedList<T> {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        write_seq(self.len(), self, out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        read_seq(bytes)
+    }
+}
+
+impl<T: FromBuildBytes + Ord> FromBuildBytes for BinaryHeap<T> {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        write_seq(self.len(), self, out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        read_seq(bytes)
+    }
+}
+
+impl<T: FromBuildBytes + Ord> FromBuildBytes for BTreeSet<T> {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        write_seq(self.len(), self, out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        read_seq(bytes)
+    }
+}
+
+impl<T, S> FromBuildBytes for HashSet<T, S>
+where
+    T: FromBuildBytes + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        write_unordered(self.len(), self, T::to_build_bytes, out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        read_seq(bytes)
+    }
+}
+
+impl<K: FromBuildBytes + Ord, V: FromBuildBytes> FromBuildBytes for BTreeMap<K, V> {
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        write_len(self.len(), out);
+        for (k, v) in self {
+            k.to_build_bytes(out);
+            v.to_build_bytes(out);
+        }
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        read_seq::<(K, V), _>(bytes)
+    }
+}
+
+impl<K, V, S> FromBuildBytes for HashMap<K, V, S>
+where
+    K: FromBuildBytes + Eq + Hash,
+    V: FromBuildBytes,
+    S: BuildHasher + Default,
+{
+    fn to_build_bytes(&self, out: &mut Vec<u8>) {
+        let write = |(k, v): (&K, &V), out: &mut Vec<u8>| {
+            k.to_build_bytes(out);
+            v.to_build_bytes(out);
+        };
+        write_unordered(self.len(), self, write, out);
+    }
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+        read_seq::<(K, V), _>(bytes)
+    }
+}
+
+macro_rules! tuples {
+    ($(($($name:ident),+))*) => {
+        $(
+            impl<$($name: FromBuildBytes),+> FromBuildBytes for ($($name,)+) {
+                #[allow(non_snake_case)]
+                fn to_build_bytes(&self, out: &mut Vec<u8>) {
+                    let ($($name,)+) = self;
+                    $($name.to_build_bytes(out);)+
+                }
+                fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self> {
+                    Some(($($name::from_build_bytes(bytes)?,)+))
+                }
+            }
+        )*
+    };
+}
+
+tuples! {
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+    (A, B, C, D, E, F, G, H, I)
+    (A, B, C, D, E, F, G, H, I, J)
+    (A, B, C, D, E, F, G, H, I, J, K)
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+}
@@ -0,0 +1,108 @@
+//! Embedding of large values as compressed bytes, rebuilt the first time they are used.
+//!
+//! Instead of its builder, a value is written as a [`LazyLock`](std::sync::LazyLock) that decompresses
+//! the bytes of the value and decodes them with [`FromBuildBytes`]. The output refers to this module,
+//! so the crate using it needs `buildstr` with the `compress` feature too.
+//!
+//! # Examples
+//! ```
+//! use buildstr::FromBuildBytes;
+//!
+//! #[derive(FromBuildBytes, Debug, PartialEq)]
+//! struct Word {
+//!     text: String,
+//!     count: u32,
+//! }
+//!
+//! let words = vec![Word { text: "a".into(), count: 10 }];
+//! let rendered = buildstr::compress::render(&words);
+//! assert!(rendered.starts_with("::std::sync::LazyLock::new(|| ::buildstr::compress::decompress(b\""));
+//!
+//! // The bytes in the output are decoded with `decompress`
+//! let bytes = buildstr::compress::compress(&words);
+//! assert_eq!(buildstr::compress::decompress::<Vec<Word>>(&bytes), words);
+//! ```
+
+mod impls;
+
+use crate::{Expr, Options};
+
+/// Trait for writing a value as bytes, and reading it back.
+///
+/// Implemented for the primitive types, `String`, the common collections, `Option`, `Result`, `Box`,
+/// tuples and arrays. It can be derived for structs and enums whose fields implement it.
+pub trait FromBuildBytes: Sized {
+    /// Appends the bytes of the value to `out`.
+    fn to_build_bytes(&self, out: &mut Vec<u8>);
+
+    /// Reads a value written by [`to_build_bytes`](Self::to_build_bytes) from the start of `bytes`,
+    /// advancing it past the value.
+    ///
+    /// Returns `None` if the bytes don't contain a valid value.
+    fn from_build_bytes(bytes: &mut &[u8]) -> Option<Self>;
+}
+
+/// Compression level used by [`compress`], from 0 to 10.
+const LEVEL: u8 = 9;
+
+/// Gets the compressed bytes of `value`.
+#[must_use]
+pub fn compress<T: FromBuildBytes>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value.to_build_bytes(&mut bytes);
+    miniz_oxide::deflate::compress_to_vec(&bytes, LEVEL)
+}
+
+/// Rebuilds a value from the bytes returned by [`compress`].
+///
+/// # Panics
+/// Panics if the bytes aren't a compressed value of type `T`.
+#[must_use]
+pub fn decompress<T: FromBuildBytes>(data: &[u8]) -> T {
+    let bytes = miniz_oxide::inflate::decompress_to_vec(data)
+        .unwrap_or_else(|e| panic!("invalid compressed data: {e}"));
+    let mut rest = &bytes[..];
+    match T::from_build_bytes(&mut rest) {
+        Some(value) if rest.is_empty() => value,
+        _ => panic!(
+            "invalid compressed data for `{}`",
+            std::any::type_name::<T>()
+        ),
+    }
+}
+
+/// Writes `value` as a `LazyLock` of its compressed bytes, in the current render.
+pub(crate) fn lazy<T: FromBuildBytes>(value: &T) -> Expr {
+    let bytes = crate::__private::byte_string(&compress(value));
+    let decompress = Expr::Closure {
+        params: Vec::new(),
+        body: Box::new(Expr::path("::buildstr::compress::decompress").call([bytes])),
+    };
+    Expr::path("::std::sync::LazyLock::new").call([decompress])
+}
+
+/// Gets a `LazyLock` that rebuilds `value` from its compressed bytes, to initialize a `static`.
+///
+/// See [`render_with`].
+#[must_use]
+pub fn render<T: FromBuildBytes>(value: &T) -> String {
+    render_with(value, &Options::default())
+}
+
+/// Same as [`render`], with the specified [`Options`].
+///
+/// The output only depends on the options that transform the whole output, like
+/// [`Options::rewrite_paths`] and [`Options::style`].
+/// To move the bytes to a sidecar file, use [`build::write_compressed_with`](crate::build::write_compressed_with).
+///
+/// # Panics
+/// Never panics in practice, as the only rendered value is the compressed bytes.
+#[must_use]
+pub fn render_with<T: FromBuildBytes>(value: &T, options: &Options) -> String {
+    let guard = crate::__private::context::enter(options.clone());
+    let expr = lazy(value);
+    // Only the values that are rendered can fail, and there are none
+    guard
+        .finish(expr)
+        .expect("compressed values can't fail to render")
+}
@@ -0,0 +1,65 @@
+//! Errors of unknown types, rebuilt from their message and source.
+
+use std::error::Error;
+use std::fmt;
+
+/// An error with a message and an optional source, used to rebuild errors behind a `dyn Error`
+/// whose type isn't known, see [`Renderer::register_error`](crate::Renderer::register_error).
+///
+/// It displays the same message as the original error, and keeps its chain of sources.
+///
+/// # Examples
+/// ```
+/// use std::error::Error;
+/// use buildstr::{BuildStr, ErrorMessage};
+///
+/// let error: Box<dyn Error + Send + Sync> = Box::new(ErrorMessage::with_source(
+///     "can't load config",
+///     ErrorMessage::new("missing field `name`"),
+/// ));
+/// assert_eq!(
+///     error.to_build_string(),
+///     concat!(
+///         "Box::new(::buildstr::ErrorMessage::with_source(\"can't load config\", ",
+///         "::buildstr::ErrorMessage::new(\"missing field `name`\"))) as Box<dyn ::std::error::Error + Send + Sync>"
+///     )
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ErrorMessage {
+    message: String,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl ErrorMessage {
+    /// Creates an error without a source.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Creates an error caused by `source`.
+    pub fn with_source(
+        message: impl Into<String>,
+        source: impl Error + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            source: Some(Box::new(source)),
+        }
+    }
+}
+
+impl fmt::Display for ErrorMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ErrorMessage {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
@@ -0,0 +1,484 @@
+//! Syntax tree of the builder expressions, used to inspect, transform and print them.
+//!
+//! An [`Expr`] is obtained with [`to_build_expr`](crate::BuildStr::to_build_expr) or built with its constructors.<br>
+//! It can then be printed with [`Display`](std::fmt::Display), pretty-printed with
+//! [`to_pretty_string`](Expr::to_pretty_string), or converted to tokens with `to_tokens`.
+//!
+//! Types and generic arguments are kept as text, as they are not expressions.
+
+mod paths;
+mod pretty;
+mod print;
+
+pub(crate) use print::compact;
+
+/// A builder expression.
+///
+/// # Examples
+/// ```
+/// use buildstr::{BuildStr, Expr};
+///
+/// let expr = vec![(1u8, 'a')].to_build_expr();
+/// let Expr::Call { args, .. } = &expr else {
+///     panic!("Vec is built with a call")
+/// };
+/// assert!(matches!(args[..], [Expr::Array(_)]));
+/// assert_eq!(expr.to_string(), "::std::vec::Vec::from_iter([(1u8, 'a'),])");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// A path, like `::core::option::Option::None` or `Color::Red`.
+    Path(Path),
+    /// A literal, like `1u8`, `"text"`, `'c'` or `true`.
+    Lit(String),
+    /// A call, like `::std::string::String::from("text")`.
+    Call {
+        func: Box<Expr>,
+        args: Vec<Expr>,
+        layout: Layout,
+    },
+    /// A chain of method calls, like `(1u8..4u8).step_by(2usize)`.
+    MethodChain {
+        receiver: Box<Expr>,
+        calls: Vec<MethodCall>,
+    },
+    /// A struct literal, like `Point{x: 1i32,y: 2i32,}`.
+    StructLit {
+        path: Path,
+        fields: Vec<(String, Expr)>,
+        layout: Layout,
+    },
+    /// A tuple, like `(1u8, 2u8)`.
+    TupleLit { items: Vec<Expr>, layout: Layout },
+    /// An array, like `[1u8,2u8,]`.
+    Array(Vec<Expr>),
+    /// An array repeating an expression, like `[0u8; 4]`.
+    Repeat { expr: Box<Expr>, len: Box<Expr> },
+    /// A reference, like `&[1u8,]`.
+    Ref { mutable: bool, expr: Box<Expr> },
+    /// A unary operation, like `-1i32`.
+    Unary { op: char, expr: Box<Expr> },
+    /// A binary operation, like `::std::time::UNIX_EPOCH + ::core::time::Duration::new(5, 0)`.
+    Binary {
+        lhs: Box<Expr>,
+        op: char,
+        rhs: Box<Expr>,
+    },
+    /// A cast, like `&[]as&[u8]`. The type is kept as text.
+    ///
+    /// Only [`Layout::Spaced`] puts spaces around the `as`.
+    Cast {
+        expr: Box<Expr>,
+        ty: String,
+        layout: Layout,
+    },
+    /// A range, like `1u8..=4u8`.
+    Range {
+        start: Option<Box<Expr>>,
+        end: Option<Box<Expr>>,
+        inclusive: bool,
+    },
+    /// A closure, like `|__w0| Node{parent: ::std::rc::Weak::clone(__w0),}`.
+    Closure {
+        params: Vec<String>,
+        body: Box<Expr>,
+    },
+    /// A block, like `{let mut map=...;map.extend([...]);map}`.
+    Block {
+        unsafety: bool,
+        stmts: Vec<Stmt>,
+        expr: Option<Box<Expr>>,
+    },
+    /// A macro call, like `::core::format_args!("text")`.
+    ///
+    /// The arguments are a [`Paren`](Expr::Paren) or a [`TupleLit`](Expr::TupleLit) for macros called with
+    /// parentheses, and an [`Array`](Expr::Array) or a [`Repeat`](Expr::Repeat) for macros called with brackets.
+    Macro { path: Path, args: Box<Expr> },
+    /// An expression in parentheses.
+    Paren(Box<Expr>),
+    /// Code kept as is, like the output of a [`Renderer`](crate::Renderer) override or of a manual
+    /// [`to_build_string`](crate::BuildStr::to_build_string).
+    Verbatim(String),
+}
+
+/// How the items of a list are separated when printed with [`Display`](std::fmt::Display).
+///
+/// It doesn't change the meaning of the expression, only how it's printed in a single line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// Like `f(a,b)` and `Range{start:a,end:b}`.
+    #[default]
+    Compact,
+    /// Like `(a, b)` and `Point{x: a, y: b}`.
+    Spaced,
+    /// Like `Point(a,b,)` and `Point{x: a,y: b,}`, the form of the derived types.
+    Terminated,
+}
+
+/// A path in expression position, like `::std::vec::Vec::<u8>::new`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    /// The qualified self type, like `::std::primitive::char as ::std::str::FromStr` in
+    /// `<::std::primitive::char as ::std::str::FromStr>::from_str`.
+    pub qself: Option<String>,
+    /// The path starts with `::`.
+    pub leading_colon: bool,
+    pub segments: Vec<Segment>,
+}
+
+/// A segment of a [`Path`], like `Vec::<u8>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub ident: String,
+    /// The generic arguments, without the turbofish, like `u8` in `Vec::<u8>`.
+    pub generics: Option<String>,
+}
+
+/// A method call in a [`Expr::MethodChain`], like `.step_by(2usize)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodCall {
+    pub name: String,
+    /// The generic arguments, without the turbofish, like `[();1]` in `.try_into::<[();1]>()`.
+    pub generics: Option<String>,
+    pub args: Vec<Expr>,
+}
+
+/// A statement in a [`Expr::Block`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stmt {
+    /// A `let` binding, like `let mut map=...;`.
+    Let {
+        mutable: bool,
+        name: String,
+        ty: Option<String>,
+        init: Expr,
+    },
+    /// A constant, like `const __CHUNK0:[u8;2]=[1u8,2u8,];`.
+    Const {
+        name: String,
+        ty: String,
+        init: Expr,
+    },
+    /// An import, like `use ::std::vec::Vec;`.
+    Use(Path),
+    /// An expression followed by a semicolon, like `map.extend([...]);`.
+    Expr(Expr),
+}
+
+/// Position of a subexpression whose parentheses depend on its precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operand {
+    /// The operand of a reference or a unary operation.
+    Prefix,
+    /// The receiver of a method, or the called function.
+    Receiver,
+    /// The operand of a cast.
+    Cast,
+    /// The left operand of a binary operation.
+    Lhs,
+    /// The right operand of a binary operation.
+    Rhs,
+    /// A bound of a range.
+    Bound,
+}
+
+/// Returns `true` if `expr` must be wrapped in parentheses to be used as `operand`,
+/// like the range in `&(1u8..2u8)`.
+pub(crate) fn needs_parens(operand: Operand, expr: &Expr) -> bool {
+    match expr {
+        Expr::Range { .. } | Expr::Closure { .. } => true,
+        Expr::Binary { .. } => !matches!(operand, Operand::Lhs | Operand::Bound),
+        Expr::Cast { .. } => matches!(operand, Operand::Prefix | Operand::Receiver),
+        Expr::Ref { .. } | Expr::Unary { .. } => operand == Operand::Receiver,
+        _ => false,
+    }
+}
+
+impl Expr {
+    /// Creates a path expression from its text, like `::std::vec::Vec::<u8>::new`.
+    ///
+    /// # Panics
+    /// Panics if `path` isn't a path.
+    ///
+    /// # Examples
+    /// ```
+    /// use buildstr::Expr;
+    ///
+    /// let expr = Expr::path("::std::string::String::new").call([]);
+    /// assert_eq!(expr.to_string(), "::std::string::String::new()");
+    /// ```
+    #[must_use]
+    pub fn path(path: &str) -> Expr {
+        Expr::Path(Path::parse(path))
+    }
+
+    /// Calls the expression with `args`, like `func(a,b)`.
+    #[must_use]
+    pub fn call(self, args: impl IntoIterator<Item = Expr>) -> Expr {
+        Expr::Call {
+            func: Box::new(self),
+            args: args.into_iter().collect(),
+            layout: Layout::Compact,
+        }
+    }
+
+    /// Calls the method `name` on the expression, like `receiver.name(a,b)`.
+    #[must_use]
+    pub fn method(self, name: &str, args: impl IntoIterator<Item = Expr>) -> Expr {
+        let call = MethodCall {
+            name: name.to_owned(),
+            generics: None,
+            args: args.into_iter().collect(),
+        };
+        match self {
+            Expr::MethodChain {
+                receiver,
+                mut calls,
+            } => {
+                calls.push(call);
+                Expr::MethodChain { receiver, calls }
+            }
+            receiver => Expr::MethodChain {
+                receiver: Box::new(receiver),
+                calls: vec![call],
+            },
+        }
+    }
+
+    /// Creates a tuple, like `(a, b)`.
+    #[must_use]
+    pub fn tuple(items: impl IntoIterator<Item = Expr>) -> Expr {
+        Expr::TupleLit {
+            items: items.into_iter().collect(),
+            layout: Layout::Spaced,
+        }
+    }
+
+    /// Creates an array, like `[a,b,]`.
+    #[must_use]
+    pub fn array(items: impl IntoIterator<Item = Expr>) -> Expr {
+        Expr::Array(items.into_iter().collect())
+    }
+
+    /// Takes a reference to the expression, like `&expr` or `&mut expr`.
+    #[must_use]
+    pub fn reference(self, mutable: bool) -> Expr {
+        Expr::Ref {
+            mutable,
+            expr: Box::new(self),
+        }
+    }
+
+    /// Casts the expression to the type `ty`, like `expr as ty`.
+    #[must_use]
+    pub fn cast(self, ty: impl Into<String>) -> Expr {
+        Expr::Cast {
+            expr: Box::new(self),
+            ty: ty.into(),
+            layout: Layout::Spaced,
+        }
+    }
+
+    /// Sets the [`Layout`] of a call, tuple, struct literal or cast. Other expressions are returned unchanged.
+    #[must_use]
+    pub fn with_layout(mut self, layout: Layout) -> Expr {
+        if let Expr::Call { layout: l, .. }
+        | Expr::StructLit { layout: l, .. }
+        | Expr::TupleLit { layout: l, .. }
+        | Expr::Cast { layout: l, .. } = &mut self
+        {
+            *l = layout;
+        }
+        self
+    }
+
+    /// Creates a block of `stmts` that evaluates to `expr`, like `{let x=a;x}`.
+    #[must_use]
+    pub fn block(stmts: Vec<Stmt>, expr: Expr) -> Expr {
+        Expr::Block {
+            unsafety: false,
+            stmts,
+            expr: Some(Box::new(expr)),
+        }
+    }
+
+    /// Creates a call of the macro `path`, like `vec![a,b,]`, with its arguments as described in [`Expr::Macro`].
+    ///
+    /// # Panics
+    /// Panics if `path` isn't a path.
+    #[must_use]
+    pub fn macro_call(path: &str, args: Expr) -> Expr {
+        Expr::Macro {
+            path: Path::parse(path),
+            args: Box::new(args),
+        }
+    }
+
+    /// Prints the expression in multiple lines, like `rustfmt` would.
+    ///
+    /// # Examples
+    /// ```
+    /// use buildstr::BuildStr;
+    ///
+    /// #[derive(BuildStr)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// let expr = Point { x: 1, y: 2 }.to_build_expr();
+    /// assert_eq!(expr.to_pretty_string(), "Point { x: 1i32, y: 2i32 }");
+    /// ```
+    #[must_use]
+    pub fn to_pretty_string(&self) -> String {
+        pretty::pretty(self)
+    }
+
+    /// Replaces the prefix `from` of all the paths with `to`, including the paths in types and in
+    /// [`Verbatim`](Expr::Verbatim) code.
+    ///
+    /// Only whole segments are replaced, so `crate::model` matches `crate::model::Point` but not `crate::models`.
+    ///
+    /// # Panics
+    /// Panics if `from` or `to` aren't paths.
+    ///
+    /// # Examples
+    /// ```
+    /// use buildstr::{Expr, Layout, Path};
+    ///
+    /// let mut expr = Expr::StructLit {
+    ///     path: Path::parse("crate::model::Point"),
+    ///     fields: vec![("x".into(), Expr::path("::core::marker::PhantomData::<crate::model::Unit>"))],
+    ///     layout: Layout::Terminated,
+    /// };
+    /// expr.rewrite_prefix("crate::model", "::model_types");
+    /// assert_eq!(
+    ///     expr.to_string(),
+    ///     "::model_types::Point{x: ::core::marker::PhantomData::<::model_types::Unit>,}"
+    /// );
+    /// ```
+    pub fn rewrite_prefix(&mut self, from: &str, to: &str) {
+        paths::rewrite(self, &Path::parse(from), &Path::parse(to));
+    }
+
+    /// Imports the types used by absolute paths (starting with `::` or `crate`) with `use` statements,
+    /// and shortens the paths to their name.
+    ///
+    /// Types with the same name as another imported type, or as a path that isn't absolute, are not imported.
+    ///
+    /// # Examples
+    /// ```
+    /// use buildstr::BuildStr;
+    ///
+    /// let mut expr = vec![Some(1u8)].to_build_expr();
+    /// expr.hoist_uses();
+    /// assert_eq!(
+    ///     expr.to_string(),
+    ///     "{use ::core::option::Option;use ::std::vec::Vec;Vec::from_iter([Option::Some(1u8),])}"
+    /// );
+    /// ```
+    pub fn hoist_uses(&mut self) {
+        paths::hoist_uses(self);
+    }
+
+    /// Converts the expression to tokens, to be returned by a procedural macro.
+    #[cfg(feature = "proc-macro")]
+    #[must_use]
+    pub fn to_tokens(&self) -> proc_macro2::TokenStream {
+        print::tokens(self)
+    }
+
+    /// Calls `f` with each direct subexpression.
+    pub(crate) fn for_each_child_mut(&mut self, f: &mut impl FnMut(&mut Expr)) {
+        match self {
+            Expr::Path(_) | Expr::Lit(_) | Expr::Verbatim(_) => {}
+            Expr::Call { func, args, .. } => {
+                f(func);
+                args.iter_mut().for_each(f);
+            }
+            Expr::MethodChain { receiver, calls } => {
+                f(receiver);
+                for call in calls {
+                    call.args.iter_mut().for_each(&mut *f);
+                }
+            }
+            Expr::StructLit { fields, .. } => {
+                for (_, value) in fields {
+                    f(value);
+                }
+            }
+            Expr::TupleLit { items, .. } | Expr::Array(items) => items.iter_mut().for_each(f),
+            Expr::Repeat { expr, len } => {
+                f(expr);
+                f(len);
+            }
+            Expr::Binary { lhs, rhs, .. } => {
+                f(lhs);
+                f(rhs);
+            }
+            Expr::Ref { expr, .. }
+            | Expr::Unary { expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Paren(expr)
+            | Expr::Closure { body: expr, .. }
+            | Expr::Macro { args: expr, .. } => f(expr),
+            Expr::Range { start, end, .. } => {
+                for expr in start.iter_mut().chain(end) {
+                    f(expr);
+                }
+            }
+            Expr::Block { stmts, expr, .. } => {
+                for stmt in stmts {
+                    match stmt {
+                        Stmt::Let { init, .. } | Stmt::Const { init, .. } | Stmt::Expr(init) => {
+                            f(init);
+                        }
+                        Stmt::Use(_) => {}
+                    }
+                }
+                if let Some(expr) = expr {
+                    f(expr);
+                }
+            }
+        }
+    }
+}
+
+impl Path {
+    /// Creates a path from its segments, like `["std", "vec", "Vec"]`.
+    pub fn new<'a>(leading_colon: bool, segments: impl IntoIterator<Item = &'a str>) -> Path {
+        Path {
+            qself: None,
+            leading_colon,
+            segments: segments
+                .into_iter()
+                .map(|ident| Segment {
+                    ident: ident.to_owned(),
+                    generics: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Parses a path from its text, like `<u8 as ::std::str::FromStr>::from_str` or `::std::vec::Vec::<u8>::new`.
+    ///
+    /// # Panics
+    /// Panics if `path` isn't a path.
+    #[must_use]
+    pub fn parse(path: &str) -> Path {
+        paths::split(path).unwrap_or_else(|| panic!("`{path}` is not a path"))
+    }
+}
+
+impl std::fmt::Display for Expr {
+    /// Prints the expression in a single line, separating the items of lists as requested by their [`Layout`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&print::canonical(self))
+    }
+}
+
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&print::canonical(&Expr::Path(self.clone())))
+    }
+}
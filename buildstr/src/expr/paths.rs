@@ -0,0 +1,383 @@
+//! Transformations of the paths of an expression, see [`Expr::rewrite_prefix`] and [`Expr::hoist_uses`].
+
+use std::collections::{BTreeMap, HashSet};
+
+use super::{Expr, Path, Segment, Stmt};
+
+/// Receives the paths of an expression, and the code kept as text (types and generic arguments).
+trait VisitMut {
+    fn path(&mut self, path: &mut Path);
+    fn text(&mut self, text: &mut String);
+
+    /// Receives the path of a `use` statement.
+    fn import(&mut self, path: &mut Path) {
+        self.path(path);
+    }
+}
+
+fn visit_path(path: &mut Path, v: &mut impl VisitMut) {
+    v.path(path);
+    if let Some(qself) = &mut path.qself {
+        v.text(qself);
+    }
+    for segment in &mut path.segments {
+        if let Some(generics) = &mut segment.generics {
+            v.text(generics);
+        }
+    }
+}
+
+fn visit(expr: &mut Expr, v: &mut impl VisitMut) {
+    match expr {
+        Expr::Path(path) => visit_path(path, v),
+        Expr::Lit(_) => {}
+        Expr::Verbatim(code) => v.text(code),
+        Expr::Call { func, args, .. } => {
+            visit(func, v);
+            for arg in args {
+                visit(arg, v);
+            }
+        }
+        Expr::MethodChain { receiver, calls } => {
+            visit(receiver, v);
+            for call in calls {
+                if let Some(generics) = &mut call.generics {
+                    v.text(generics);
+                }
+                for arg in &mut call.args {
+                    visit(arg, v);
+                }
+            }
+        }
+        Expr::StructLit { path, fields, .. } => {
+            visit_path(path, v);
+            for (_, value) in fields {
+                visit(value, v);
+            }
+        }
+        Expr::TupleLit { items, .. } | Expr::Array(items) => {
+            for item in items {
+                visit(item, v);
+            }
+        }
+        Expr::Repeat {
+            expr: lhs,
+            len: rhs,
+        }
+        | Expr::Binary { lhs, rhs, .. } => {
+            visit(lhs, v);
+            visit(rhs, v);
+        }
+        Expr::Ref { expr, .. } | Expr::Unary { expr, .. } | Expr::Paren(expr) => visit(expr, v),
+        Expr::Closure { body, .. } => visit(body, v),
+        Expr::Cast { expr, ty, .. } => {
+            visit(expr, v);
+            v.text(ty);
+        }
+        Expr::Range { start, end, .. } => {
+            for expr in start.iter_mut().chain(end) {
+                visit(expr, v);
+            }
+        }
+        Expr::Macro { path, args } => {
+            visit_path(path, v);
+            visit(args, v);
+        }
+        Expr::Block { stmts, expr, .. } => {
+            for stmt in stmts {
+                match stmt {
+                    Stmt::Let { ty, init, .. } => {
+                        if let Some(ty) = ty {
+                            v.text(ty);
+                        }
+                        visit(init, v);
+                    }
+                    Stmt::Const { ty, init, .. } => {
+                        v.text(ty);
+                        visit(init, v);
+                    }
+                    Stmt::Use(path) => v.import(path),
+                    Stmt::Expr(expr) => visit(expr, v),
+                }
+            }
+            if let Some(expr) = expr {
+                visit(expr, v);
+            }
+        }
+    }
+}
+
+/// Replaces the path `from` with `to` in `text`, when it's not part of a longer identifier or path.
+fn rewrite_text(text: &mut String, from: &str, to: &str) {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (i, _) in text.match_indices(from) {
+        let before = text[..i].chars().last();
+        let after = text[i + from.len()..].chars().next();
+        let starts = before.is_none_or(|c| !is_ident(c) && (c != ':' || from.starts_with(':')));
+        if starts && after.is_none_or(|c| !is_ident(c)) {
+            out.push_str(&text[last..i]);
+            out.push_str(to);
+            last = i + from.len();
+        }
+    }
+    out.push_str(&text[last..]);
+    *text = out;
+}
+
+struct Rewrite<'a> {
+    from: &'a Path,
+    to: &'a Path,
+    text: (String, String),
+}
+
+impl VisitMut for Rewrite<'_> {
+    fn path(&mut self, path: &mut Path) {
+        let len = self.from.segments.len();
+        let matches = path.qself.is_none()
+            && path.leading_colon == self.from.leading_colon
+            && path.segments.len() >= len
+            && path
+                .segments
+                .iter()
+                .zip(&self.from.segments)
+                .enumerate()
+                .all(|(i, (s, f))| s.ident == f.ident && (s.generics.is_none() || i == len - 1));
+        if matches {
+            let generics = path.segments[len - 1].generics.take();
+            let mut segments = self.to.segments.clone();
+            if let Some(last) = segments.last_mut() {
+                last.generics = last.generics.take().or(generics);
+            }
+            segments.extend(path.segments.drain(len..));
+            path.segments = segments;
+            path.leading_colon = self.to.leading_colon;
+        }
+    }
+
+    fn text(&mut self, text: &mut String) {
+        rewrite_text(text, &self.text.0, &self.text.1);
+    }
+}
+
+/// Gets the index of the `>` closing the `<` before `s`.
+fn closing_angle(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut last = None;
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            // `->` in function types
+            '>' if last == Some('-') => {}
+            '>' if depth == 0 => return Some(i),
+            '>' => depth -= 1,
+            _ => {}
+        }
+        last = Some(c);
+    }
+    None
+}
+
+/// Splits the text of a path into its segments, see [`Path::parse`].
+pub(super) fn split(s: &str) -> Option<Path> {
+    let mut rest = s.trim();
+    let mut qself = None;
+    if let Some(inner) = rest.strip_prefix('<') {
+        let end = closing_angle(inner)?;
+        qself = Some(inner[..end].trim().to_owned());
+        rest = inner[end + 1..].trim_start().strip_prefix("::")?;
+    }
+    let leading_colon = qself.is_none() && rest.starts_with("::");
+    if leading_colon {
+        rest = &rest[2..];
+    }
+    let mut segments = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '#')))
+            .unwrap_or(rest.len());
+        let ident = &rest[..len];
+        if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        rest = rest[len..].trim_start();
+        let mut generics = None;
+        let turbofish = rest.strip_prefix("::").map(str::trim_start);
+        if let Some(inner) = turbofish.and_then(|r| r.strip_prefix('<')) {
+            let end = closing_angle(inner)?;
+            generics = Some(inner[..end].trim().to_owned());
+            rest = inner[end + 1..].trim_start();
+        }
+        segments.push(Segment {
+            ident: ident.to_owned(),
+            generics,
+        });
+        match rest.strip_prefix("::") {
+            Some(next) => rest = next,
+            None if rest.is_empty() => break,
+            None => return None,
+        }
+    }
+    Some(Path {
+        qself,
+        leading_colon,
+        segments,
+    })
+}
+
+pub(super) fn rewrite(expr: &mut Expr, from: &Path, to: &Path) {
+    let text = (from.to_string(), to.to_string());
+    visit(expr, &mut Rewrite { from, to, text });
+}
+
+fn is_primitive(ident: &str) -> bool {
+    matches!(
+        ident,
+        "primitive"
+            | "bool"
+            | "char"
+            | "str"
+            | "f32"
+            | "f64"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+    )
+}
+
+/// Gets the index of the segment of `path` that can be imported, the first one that is a type or a variant.
+///
+/// The items of primitive types, like `::core::primitive::f64::NAN`, are associated items and can't be imported.
+fn import(path: &Path) -> Option<usize> {
+    let absolute = path.leading_colon || path.segments.first().is_some_and(|s| s.ident == "crate");
+    if path.qself.is_some() || !absolute || path.segments.iter().any(|s| is_primitive(&s.ident)) {
+        return None;
+    }
+    let i = path
+        .segments
+        .iter()
+        .position(|s| s.ident.starts_with(char::is_uppercase))?;
+    (i > 0 && path.segments[..i].iter().all(|s| s.generics.is_none())).then_some(i)
+}
+
+/// Finds the types to import, by name.
+#[derive(Default)]
+struct Collect {
+    imports: BTreeMap<String, Path>,
+    conflicts: HashSet<String>,
+}
+
+impl VisitMut for Collect {
+    fn path(&mut self, path: &mut Path) {
+        let Some(i) = import(path) else {
+            // Names used without a path can't be imported
+            if !path.leading_colon {
+                self.conflicts.insert(path.segments[0].ident.clone());
+            }
+            return;
+        };
+        let mut imported = path.clone();
+        imported.segments.truncate(i + 1);
+        imported.segments[i].generics = None;
+        let name = imported.segments[i].ident.clone();
+        match self.imports.get(&name) {
+            Some(existing) if *existing != imported => {
+                self.conflicts.insert(name);
+            }
+            _ => {
+                self.imports.insert(name, imported);
+            }
+        }
+    }
+
+    fn text(&mut self, _: &mut String) {}
+
+    fn import(&mut self, path: &mut Path) {
+        // Already imported
+        if let Some(segment) = path.segments.last() {
+            self.conflicts.insert(segment.ident.clone());
+        }
+    }
+}
+
+/// Shortens the paths of the imported types.
+struct Shorten {
+    imports: BTreeMap<String, Path>,
+    /// The imported paths as text, to shorten them in types.
+    texts: Vec<(String, String)>,
+}
+
+impl VisitMut for Shorten {
+    fn path(&mut self, path: &mut Path) {
+        let Some(i) = import(path) else {
+            return;
+        };
+        let name = &path.segments[i].ident;
+        let imported = self.imports.get(name).is_some_and(|imported| {
+            imported.leading_colon == path.leading_colon
+                && imported.segments[..i]
+                    .iter()
+                    .zip(&path.segments)
+                    .all(|(a, b)| a.ident == b.ident)
+        });
+        if imported {
+            path.leading_colon = false;
+            path.segments.drain(..i);
+        }
+    }
+
+    fn text(&mut self, text: &mut String) {
+        for (from, to) in &self.texts {
+            rewrite_text(text, from, to);
+        }
+    }
+
+    fn import(&mut self, _: &mut Path) {}
+}
+
+pub(super) fn hoist_uses(expr: &mut Expr) {
+    let mut collect = Collect::default();
+    visit(expr, &mut collect);
+    let mut imports = collect.imports;
+    imports.retain(|name, _| !collect.conflicts.contains(name));
+    if imports.is_empty() {
+        return;
+    }
+
+    let texts = imports
+        .iter()
+        .map(|(name, path)| (path.to_string(), name.clone()))
+        .collect();
+    let mut shorten = Shorten { imports, texts };
+    visit(expr, &mut shorten);
+
+    let mut uses = shorten.imports.into_values().collect::<Vec<_>>();
+    uses.sort_by_key(ToString::to_string);
+    let uses = uses.into_iter().map(Stmt::Use);
+    if let Expr::Block {
+        unsafety: false,
+        stmts,
+        ..
+    } = expr
+    {
+        stmts.splice(0..0, uses);
+    } else {
+        let inner = std::mem::replace(expr, Expr::Verbatim(String::new()));
+        *expr = Expr::Block {
+            unsafety: false,
+            stmts: uses.collect(),
+            expr: Some(Box::new(inner)),
+        };
+    }
+}
@@ -0,0 +1,357 @@
+//! Multi-line printer of the syntax tree, following the layout of `rustfmt`.
+//!
+//! Expressions are printed in a single line if they fit in the maximum width,
+//! otherwise their elements are printed one per line.
+
+use std::fmt::Write;
+
+use super::{needs_parens, Expr, MethodCall, Operand, Path, Stmt};
+
+const WIDTH: usize = 100;
+const INDENT: &str = "    ";
+
+pub(super) fn pretty(expr: &Expr) -> String {
+    let mut printer = Printer {
+        out: String::new(),
+        indent: 0,
+    };
+    printer.expr(expr);
+    printer.out
+}
+
+fn join(items: &[Expr]) -> Option<String> {
+    items
+        .iter()
+        .map(flat)
+        .collect::<Option<Vec<_>>>()
+        .map(|items| items.join(", "))
+}
+
+fn generics(generics: Option<&String>) -> String {
+    generics.map_or_else(String::new, |g| format!("::<{g}>"))
+}
+
+fn method(call: &MethodCall) -> Option<String> {
+    Some(format!(
+        ".{}{}({})",
+        call.name,
+        generics(call.generics.as_ref()),
+        join(&call.args)?
+    ))
+}
+
+fn stmt(stmt: &Stmt) -> Option<String> {
+    match stmt {
+        Stmt::Let {
+            mutable,
+            name,
+            ty,
+            init,
+        } => Some(format!(
+            "let {}{name}{} = {};",
+            if *mutable { "mut " } else { "" },
+            ty.as_ref().map_or_else(String::new, |ty| format!(": {ty}")),
+            flat(init)?
+        )),
+        Stmt::Const { name, ty, init } => Some(format!("const {name}: {ty} = {};", flat(init)?)),
+        Stmt::Use(path) => Some(format!("use {path};")),
+        Stmt::Expr(expr) => Some(format!("{};", flat(expr)?)),
+    }
+}
+
+/// Like [`flat`], wrapping `expr` in parentheses if its precedence requires it in the position `operand`.
+fn flat_operand(expr: &Expr, operand: Operand) -> Option<String> {
+    let flat = flat(expr)?;
+    Some(if needs_parens(operand, expr) {
+        format!("({flat})")
+    } else {
+        flat
+    })
+}
+
+fn flat_bound(bound: Option<&Expr>) -> Option<String> {
+    bound.map_or(Some(String::new()), |bound| {
+        flat_operand(bound, Operand::Bound)
+    })
+}
+
+/// Prints `expr` in a single line, or returns `None` if it must span multiple lines, like blocks with statements.
+fn flat(expr: &Expr) -> Option<String> {
+    Some(match expr {
+        Expr::Macro { path, args } => format!("{path}!{}", flat(args)?),
+        Expr::Path(_) | Expr::Lit(_) | Expr::Verbatim(_) => expr.to_string(),
+        Expr::Call { func, args, .. } => {
+            format!("{}({})", flat_operand(func, Operand::Receiver)?, join(args)?)
+        }
+        Expr::MethodChain { receiver, calls } => {
+            let calls = calls.iter().map(method).collect::<Option<String>>()?;
+            format!("{}{calls}", flat_operand(receiver, Operand::Receiver)?)
+        }
+        Expr::StructLit { path, fields, .. } if fields.is_empty() => format!("{path} {{}}"),
+        Expr::StructLit { path, fields, .. } => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| Some(format!("{name}: {}", flat(value)?)))
+                .collect::<Option<Vec<_>>>()?;
+            format!("{path} {{ {} }}", fields.join(", "))
+        }
+        Expr::TupleLit { items, .. } if items.len() == 1 => format!("({},)", flat(&items[0])?),
+        Expr::TupleLit { items, .. } => format!("({})", join(items)?),
+        Expr::Array(items) => format!("[{}]", join(items)?),
+        Expr::Repeat { expr, len } => format!("[{}; {}]", flat(expr)?, flat(len)?),
+        Expr::Ref { mutable, expr } => {
+            format!(
+                "&{}{}",
+                if *mutable { "mut " } else { "" },
+                flat_operand(expr, Operand::Prefix)?
+            )
+        }
+        Expr::Unary { op, expr } => format!("{op}{}", flat_operand(expr, Operand::Prefix)?),
+        Expr::Binary { lhs, op, rhs } => format!(
+            "{} {op} {}",
+            flat_operand(lhs, Operand::Lhs)?,
+            flat_operand(rhs, Operand::Rhs)?
+        ),
+        Expr::Cast { expr, ty, .. } => format!("{} as {ty}", flat_operand(expr, Operand::Cast)?),
+        Expr::Range {
+            start,
+            end,
+            inclusive,
+        } => format!(
+            "{}{}{}",
+            flat_bound(start.as_deref())?,
+            if *inclusive { "..=" } else { ".." },
+            flat_bound(end.as_deref())?
+        ),
+        Expr::Closure { params, body } => format!("|{}| {}", params.join(", "), flat(body)?),
+        Expr::Block { stmts, .. } if !stmts.is_empty() => return None,
+        Expr::Block { unsafety, expr, .. } => format!(
+            "{}{}",
+            if *unsafety { "unsafe " } else { "" },
+            match expr {
+                Some(expr) => format!("{{ {} }}", flat(expr)?),
+                None => "{}".to_owned(),
+            }
+        ),
+        Expr::Paren(expr) => format!("({})", flat(expr)?),
+    })
+}
+
+/// Returns `true` if `expr` can start in the line of its parent, like the array in `Vec::from_iter([`.
+fn overflows(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Array(_) | Expr::StructLit { .. } | Expr::Block { .. } | Expr::Closure { .. }
+    )
+}
+
+struct Printer {
+    out: String,
+    indent: usize,
+}
+
+impl Printer {
+    fn column(&self) -> usize {
+        let line = self.out.rsplit('\n').next().unwrap_or_default();
+        line.chars().count()
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        self.out.push_str(&INDENT.repeat(self.indent));
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        if let Some(flat) = flat(expr) {
+            if self.column() + flat.len() <= WIDTH {
+                self.out.push_str(&flat);
+                return;
+            }
+        }
+        match expr {
+            Expr::Macro { path, args } => match &**args {
+                Expr::TupleLit { items, .. } => {
+                    let _ = write!(self.out, "{path}!");
+                    self.list('(', items, ')', true);
+                }
+                args => self.prefixed(&format!("{path}!"), args),
+            },
+            Expr::Path(_) | Expr::Lit(_) | Expr::Verbatim(_) => {
+                self.out.push_str(&expr.to_string());
+            }
+            Expr::Call { func, args, .. } => {
+                self.operand(func, Operand::Receiver);
+                self.list('(', args, ')', true);
+            }
+            Expr::MethodChain { receiver, calls } => {
+                self.operand(receiver, Operand::Receiver);
+                self.indent += 1;
+                for call in calls {
+                    self.newline();
+                    self.out.push('.');
+                    self.out.push_str(&call.name);
+                    self.out.push_str(&generics(call.generics.as_ref()));
+                    self.list('(', &call.args, ')', true);
+                }
+                self.indent -= 1;
+            }
+            Expr::StructLit { path, fields, .. } => self.struct_lit(path, fields),
+            Expr::TupleLit { items, .. } => self.list('(', items, ')', false),
+            Expr::Array(items) => self.list('[', items, ']', false),
+            Expr::Repeat { expr, len } => {
+                self.out.push('[');
+                self.expr(expr);
+                self.out.push_str("; ");
+                self.expr(len);
+                self.out.push(']');
+            }
+            Expr::Ref { mutable, expr } => {
+                self.out.push_str(if *mutable { "&mut " } else { "&" });
+                self.operand(expr, Operand::Prefix);
+            }
+            Expr::Unary { op, expr } => {
+                self.out.push(*op);
+                self.operand(expr, Operand::Prefix);
+            }
+            Expr::Binary { lhs, op, rhs } => {
+                self.operand(lhs, Operand::Lhs);
+                let _ = write!(self.out, " {op} ");
+                self.operand(rhs, Operand::Rhs);
+            }
+            Expr::Cast { expr, ty, .. } => {
+                self.operand(expr, Operand::Cast);
+                self.out.push_str(" as ");
+                self.out.push_str(ty);
+            }
+            Expr::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                if let Some(start) = start {
+                    self.operand(start, Operand::Bound);
+                }
+                self.out.push_str(if *inclusive { "..=" } else { ".." });
+                if let Some(end) = end {
+                    self.operand(end, Operand::Bound);
+                }
+            }
+            Expr::Closure { params, body } => {
+                let _ = write!(self.out, "|{}| ", params.join(", "));
+                self.expr(body);
+            }
+            Expr::Block {
+                unsafety,
+                stmts,
+                expr,
+            } => self.block(*unsafety, stmts, expr.as_deref()),
+            Expr::Paren(expr) => {
+                self.out.push('(');
+                self.expr(expr);
+                self.out.push(')');
+            }
+        }
+    }
+
+    fn struct_lit(&mut self, path: &Path, fields: &[(String, Expr)]) {
+        let _ = write!(self.out, "{path} {{");
+        self.indent += 1;
+        for (name, value) in fields {
+            self.newline();
+            self.out.push_str(name);
+            self.out.push_str(": ");
+            self.expr(value);
+            self.out.push(',');
+        }
+        self.indent -= 1;
+        self.newline();
+        self.out.push('}');
+    }
+
+    /// Prints `expr`, wrapped in parentheses if its precedence requires it in the position `operand`.
+    fn operand(&mut self, expr: &Expr, operand: Operand) {
+        if needs_parens(operand, expr) {
+            self.out.push('(');
+            self.expr(expr);
+            self.out.push(')');
+        } else {
+            self.expr(expr);
+        }
+    }
+
+    fn prefixed(&mut self, prefix: &str, expr: &Expr) {
+        self.out.push_str(prefix);
+        self.expr(expr);
+    }
+
+    fn block(&mut self, unsafety: bool, stmts: &[Stmt], expr: Option<&Expr>) {
+        self.out.push_str(if unsafety { "unsafe {" } else { "{" });
+        self.indent += 1;
+        for s in stmts {
+            self.newline();
+            match (stmt(s), s) {
+                (Some(flat), _) if self.column() + flat.len() <= WIDTH => {
+                    self.out.push_str(&flat);
+                }
+                (
+                    _,
+                    Stmt::Let {
+                        mutable,
+                        name,
+                        ty,
+                        init,
+                    },
+                ) => {
+                    self.out
+                        .push_str(if *mutable { "let mut " } else { "let " });
+                    self.out.push_str(name);
+                    if let Some(ty) = ty {
+                        let _ = write!(self.out, ": {ty}");
+                    }
+                    self.out.push_str(" = ");
+                    self.expr(init);
+                    self.out.push(';');
+                }
+                (_, Stmt::Const { name, ty, init }) => {
+                    let _ = write!(self.out, "const {name}: {ty} = ");
+                    self.expr(init);
+                    self.out.push(';');
+                }
+                (_, Stmt::Use(path)) => {
+                    let _ = write!(self.out, "use {path};");
+                }
+                (_, Stmt::Expr(expr)) => {
+                    self.expr(expr);
+                    self.out.push(';');
+                }
+            }
+        }
+        if let Some(expr) = expr {
+            self.newline();
+            self.expr(expr);
+        }
+        self.indent -= 1;
+        self.newline();
+        self.out.push('}');
+    }
+
+    /// Prints the items one per line, or the only item in the same line if it [`overflows`] and `overflow` is set.
+    fn list(&mut self, open: char, items: &[Expr], close: char, overflow: bool) {
+        self.out.push(open);
+        match items {
+            [] => {}
+            [item] if overflow && overflows(item) => self.expr(item),
+            _ => {
+                self.indent += 1;
+                for item in items {
+                    self.newline();
+                    self.expr(item);
+                    self.out.push(',');
+                }
+                self.indent -= 1;
+                self.newline();
+            }
+        }
+        self.out.push(close);
+    }
+}
@@ -0,0 +1,446 @@
+//! Single-line printers of the syntax tree, to a string or to tokens.
+//!
+//! Both go through [`walk`], which emits the expression token by token.
+
+use super::{needs_parens, Expr, Layout, MethodCall, Operand, Path, Stmt};
+
+/// Receives the tokens of an expression.
+trait Sink {
+    fn ident(&mut self, s: &str);
+    /// Punctuation, like `::` or `,`.
+    fn punct(&mut self, s: &str);
+    fn lit(&mut self, s: &str);
+    fn open(&mut self, delimiter: char);
+    fn close(&mut self, delimiter: char);
+    /// Code kept as text, like types.
+    fn text(&mut self, s: &str);
+    /// Whitespace of the canonical form, like the one after `,` in a [`Layout::Spaced`] list.
+    fn space(&mut self) {}
+}
+
+fn separated<'a, S: Sink>(sink: &mut S, items: impl IntoIterator<Item = &'a Expr>, layout: Layout) {
+    let mut len = 0;
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            sink.punct(",");
+            if layout == Layout::Spaced {
+                sink.space();
+            }
+        }
+        walk(item, sink);
+        len += 1;
+    }
+    if layout == Layout::Terminated && len > 0 {
+        sink.punct(",");
+    }
+}
+
+/// Walks `expr` in the position `operand`, wrapping it in parentheses if its precedence requires it.
+fn operand(expr: &Expr, operand: Operand, sink: &mut impl Sink) {
+    if needs_parens(operand, expr) {
+        sink.open('(');
+        walk(expr, sink);
+        sink.close(')');
+    } else {
+        walk(expr, sink);
+    }
+}
+
+fn turbofish(generics: &str, sink: &mut impl Sink) {
+    sink.punct("::");
+    sink.punct("<");
+    sink.text(generics);
+    sink.punct(">");
+}
+
+fn path(path: &Path, sink: &mut impl Sink) {
+    if let Some(qself) = &path.qself {
+        sink.punct("<");
+        sink.text(qself);
+        sink.punct(">");
+        sink.punct("::");
+    } else if path.leading_colon {
+        sink.punct("::");
+    }
+    for (i, segment) in path.segments.iter().enumerate() {
+        if i > 0 {
+            sink.punct("::");
+        }
+        sink.ident(&segment.ident);
+        if let Some(generics) = &segment.generics {
+            turbofish(generics, sink);
+        }
+    }
+}
+
+fn method(call: &MethodCall, sink: &mut impl Sink) {
+    sink.punct(".");
+    sink.ident(&call.name);
+    if let Some(generics) = &call.generics {
+        turbofish(generics, sink);
+    }
+    sink.open('(');
+    separated(sink, &call.args, Layout::Compact);
+    sink.close(')');
+}
+
+fn block(unsafety: bool, stmts: &[Stmt], expr: Option<&Expr>, sink: &mut impl Sink) {
+    if unsafety {
+        sink.ident("unsafe");
+    }
+    sink.open('{');
+    for stmt in stmts {
+        match stmt {
+            Stmt::Let {
+                mutable,
+                name,
+                ty,
+                init,
+            } => {
+                sink.ident("let");
+                if *mutable {
+                    sink.ident("mut");
+                }
+                sink.ident(name);
+                if let Some(ty) = ty {
+                    sink.punct(":");
+                    sink.text(ty);
+                }
+                sink.punct("=");
+                walk(init, sink);
+            }
+            Stmt::Const { name, ty, init } => {
+                sink.ident("const");
+                sink.ident(name);
+                sink.punct(":");
+                sink.text(ty);
+                sink.punct("=");
+                walk(init, sink);
+            }
+            Stmt::Use(p) => {
+                sink.ident("use");
+                sink.space();
+                path(p, sink);
+            }
+            Stmt::Expr(expr) => walk(expr, sink),
+        }
+        sink.punct(";");
+    }
+    if let Some(expr) = expr {
+        walk(expr, sink);
+    }
+    sink.close('}');
+}
+
+#[allow(clippy::too_many_lines)]
+fn walk(expr: &Expr, sink: &mut impl Sink) {
+    match expr {
+        Expr::Path(p) => path(p, sink),
+        Expr::Lit(lit) if lit == "true" || lit == "false" => sink.ident(lit),
+        Expr::Lit(lit) => sink.lit(lit),
+        Expr::Call { func, args, layout } => {
+            operand(func, Operand::Receiver, sink);
+            sink.open('(');
+            separated(sink, args, *layout);
+            sink.close(')');
+        }
+        Expr::MethodChain { receiver, calls } => {
+            operand(receiver, Operand::Receiver, sink);
+            for call in calls {
+                method(call, sink);
+            }
+        }
+        Expr::StructLit {
+            path: p,
+            fields,
+            layout,
+        } => {
+            path(p, sink);
+            sink.open('{');
+            for (i, (name, value)) in fields.iter().enumerate() {
+                if i > 0 && *layout != Layout::Terminated {
+                    sink.punct(",");
+                    if *layout == Layout::Spaced {
+                        sink.space();
+                    }
+                }
+                sink.ident(name);
+                sink.punct(":");
+                if *layout != Layout::Compact {
+                    sink.space();
+                }
+                walk(value, sink);
+                if *layout == Layout::Terminated {
+                    sink.punct(",");
+                }
+            }
+            sink.close('}');
+        }
+        Expr::TupleLit { items, layout } => {
+            sink.open('(');
+            separated(sink, items, *layout);
+            if items.len() == 1 && *layout != Layout::Terminated {
+                sink.punct(",");
+            }
+            sink.close(')');
+        }
+        Expr::Array(items) => {
+            sink.open('[');
+            separated(sink, items, Layout::Terminated);
+            sink.close(']');
+        }
+        Expr::Repeat { expr, len } => {
+            sink.open('[');
+            walk(expr, sink);
+            sink.punct(";");
+            sink.space();
+            walk(len, sink);
+            sink.close(']');
+        }
+        Expr::Ref { mutable, expr } => {
+            sink.punct("&");
+            if *mutable {
+                sink.ident("mut");
+                sink.space();
+            }
+            operand(expr, Operand::Prefix, sink);
+        }
+        Expr::Unary { op, expr } => {
+            sink.punct(op.encode_utf8(&mut [0; 4]));
+            operand(expr, Operand::Prefix, sink);
+        }
+        Expr::Binary { lhs, op, rhs } => {
+            operand(lhs, Operand::Lhs, sink);
+            sink.space();
+            sink.punct(op.encode_utf8(&mut [0; 4]));
+            sink.space();
+            operand(rhs, Operand::Rhs, sink);
+        }
+        Expr::Cast { expr, ty, layout } => {
+            operand(expr, Operand::Cast, sink);
+            if *layout == Layout::Spaced {
+                sink.space();
+            }
+            sink.ident("as");
+            if *layout == Layout::Spaced {
+                sink.space();
+            }
+            sink.text(ty);
+        }
+        Expr::Range {
+            start,
+            end,
+            inclusive,
+        } => {
+            if let Some(start) = start {
+                operand(start, Operand::Bound, sink);
+            }
+            sink.punct(if *inclusive { "..=" } else { ".." });
+            if let Some(end) = end {
+                operand(end, Operand::Bound, sink);
+            }
+        }
+        Expr::Closure { params, body } => {
+            if params.is_empty() {
+                sink.punct("||");
+            } else {
+                sink.punct("|");
+                for (i, param) in params.iter().enumerate() {
+                    if i > 0 {
+                        sink.punct(",");
+                    }
+                    sink.ident(param);
+                }
+                sink.punct("|");
+            }
+            if !matches!(**body, Expr::Block { .. }) {
+                sink.space();
+            }
+            walk(body, sink);
+        }
+        Expr::Block {
+            unsafety,
+            stmts,
+            expr,
+        } => block(*unsafety, stmts, expr.as_deref(), sink),
+        Expr::Macro { path: p, args } => {
+            path(p, sink);
+            sink.punct("!");
+            walk(args, sink);
+        }
+        Expr::Paren(expr) => {
+            sink.open('(');
+            walk(expr, sink);
+            sink.close(')');
+        }
+        Expr::Verbatim(code) => sink.text(code),
+    }
+}
+
+/// Writes the tokens without whitespace, unless it's needed to separate them or requested by the [`Layout`].
+#[derive(Default)]
+struct Compact {
+    out: String,
+    /// Writes the whitespace of the canonical form.
+    spaces: bool,
+    /// The last token ends in a word (an identifier or a literal).
+    word: bool,
+}
+
+impl Compact {
+    fn push(&mut self, s: &str, starts_word: bool, ends_word: bool) {
+        let last = self.out.chars().last();
+        let first = s.chars().next();
+        // `a: ::b`, `|a| |b| c` and words need a space to not be merged
+        if (self.word && starts_word)
+            || (last.is_some() && last == first && matches!(first, Some(':' | '|')))
+        {
+            self.out.push(' ');
+        }
+        self.out.push_str(s);
+        self.word = ends_word;
+    }
+}
+
+fn is_word(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '"' | '\''))
+}
+
+impl Sink for Compact {
+    fn ident(&mut self, s: &str) {
+        self.push(s, true, true);
+    }
+
+    fn punct(&mut self, s: &str) {
+        self.push(s, false, false);
+    }
+
+    fn lit(&mut self, s: &str) {
+        self.push(s, true, true);
+    }
+
+    fn open(&mut self, delimiter: char) {
+        self.push(delimiter.encode_utf8(&mut [0; 4]), false, false);
+    }
+
+    fn close(&mut self, delimiter: char) {
+        self.push(delimiter.encode_utf8(&mut [0; 4]), false, false);
+    }
+
+    fn text(&mut self, s: &str) {
+        self.push(s, is_word(s.chars().next()), is_word(s.chars().last()));
+    }
+
+    fn space(&mut self) {
+        if self.spaces {
+            self.out.push(' ');
+            self.word = false;
+        }
+    }
+}
+
+/// Prints the expression in a single line, with the whitespace of the [`Layout`]s.
+pub(super) fn canonical(expr: &Expr) -> String {
+    let mut sink = Compact {
+        spaces: true,
+        ..Compact::default()
+    };
+    walk(expr, &mut sink);
+    sink.out
+}
+
+/// Prints the expression in a single line, without whitespace.
+pub(crate) fn compact(expr: &Expr) -> String {
+    let mut sink = Compact::default();
+    walk(expr, &mut sink);
+    sink.out
+}
+
+#[cfg(feature = "proc-macro")]
+mod tokens {
+    use proc_macro2::{
+        Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree,
+    };
+
+    use super::Sink;
+
+    /// Builds a [`TokenStream`], with a stream for each open group.
+    pub(super) struct Tokens {
+        pub(super) groups: Vec<(char, Vec<TokenTree>)>,
+    }
+
+    impl Tokens {
+        fn push(&mut self, tree: impl Into<TokenTree>) {
+            self.groups
+                .last_mut()
+                .expect("the outermost stream always exists")
+                .1
+                .push(tree.into());
+        }
+    }
+
+    impl Sink for Tokens {
+        fn ident(&mut self, s: &str) {
+            let ident = match s.strip_prefix("r#") {
+                Some(raw) => Ident::new_raw(raw, Span::call_site()),
+                None => Ident::new(s, Span::call_site()),
+            };
+            self.push(ident);
+        }
+
+        fn punct(&mut self, s: &str) {
+            let mut chars = s.chars().peekable();
+            while let Some(c) = chars.next() {
+                let spacing = if chars.peek().is_some() {
+                    Spacing::Joint
+                } else {
+                    Spacing::Alone
+                };
+                self.push(Punct::new(c, spacing));
+            }
+        }
+
+        fn lit(&mut self, s: &str) {
+            match s.parse::<Literal>() {
+                Ok(lit) => self.push(lit),
+                Err(_) => self.text(s),
+            }
+        }
+
+        fn open(&mut self, delimiter: char) {
+            self.groups.push((delimiter, Vec::new()));
+        }
+
+        fn close(&mut self, _: char) {
+            let (delimiter, trees) = self.groups.pop().expect("the group was opened");
+            let delimiter = match delimiter {
+                '(' => Delimiter::Parenthesis,
+                '[' => Delimiter::Bracket,
+                _ => Delimiter::Brace,
+            };
+            self.push(Group::new(delimiter, trees.into_iter().collect()));
+        }
+
+        fn text(&mut self, s: &str) {
+            let stream = s
+                .parse::<TokenStream>()
+                .unwrap_or_else(|e| panic!("invalid code `{s}`: {e}"));
+            for tree in stream {
+                self.push(tree);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "proc-macro")]
+pub(super) fn tokens(expr: &Expr) -> proc_macro2::TokenStream {
+    let mut sink = tokens::Tokens {
+        groups: vec![('\0', Vec::new())],
+    };
+    walk(expr, &mut sink);
+    let (_, trees) = sink
+        .groups
+        .pop()
+        .expect("the outermost stream always exists");
+    trees.into_iter().collect()
+}
@@ -4,6 +4,8 @@
 
 #[cfg(feature = "derive")]
 pub use buildstr_derive::BuildStr;
+#[cfg(feature = "compress")]
+pub use buildstr_derive::FromBuildBytes;
 
 pub use buildstr_derive::impl_buildstr;
 
@@ -11,6 +13,9 @@ extern crate self as buildstr;
 
 /// Transforms an iterable of a single value to an array-like sequence without the enclosing brackets.
 ///
+/// A number literal whose suffix is written in an element fixes the type of the literals at the same
+/// position in the next elements, so they may omit their suffixes, see [`Options::suffixes`].
+///
 /// # Examples
 /// ```
 /// use buildstr::BuildStr;
@@ -24,9 +29,11 @@ macro_rules! array_to_build_string {
     ($array:expr) => {{
         let mut s = String::new();
         let array = $array;
-        let array = array.iter().map(|x| x.to_build_string());
+        let array = $crate::__private::render_elements(array.iter(), |x| {
+            $crate::__private::dedup(x, x.to_build_expr())
+        });
         for a in array {
-            s.push_str(&a);
+            s.push_str(&a.to_string());
             s.push(',');
         }
         s
@@ -35,6 +42,8 @@ macro_rules! array_to_build_string {
 
 /// Transforms an iterable of a tuple of size two to an array-like sequence without the enclosing brackets.
 ///
+/// Like in [`array_to_build_string`], the literals already suffixed in an earlier entry may omit their suffixes.
+///
 /// # Examples
 /// ```
 /// use buildstr::map_to_build_string;
@@ -47,18 +56,80 @@ macro_rules! array_to_build_string {
 macro_rules! map_to_build_string {
     ($map:ident) => {{
         let mut s = String::new();
-        let map = $map
-            .iter()
-            .map(|(k, v)| format!("({},{})", k.to_build_string(), v.to_build_string()));
+        let map = $crate::__private::render_elements($map.iter(), |(k, v)| {
+            $crate::Expr::tuple([
+                $crate::__private::dedup(k, k.to_build_expr()),
+                $crate::__private::dedup(v, v.to_build_expr()),
+            ])
+            .with_layout($crate::Layout::Compact)
+        });
         for m in map {
-            s.push_str(&m);
+            s.push_str(&m.to_string());
             s.push(',');
         }
         s
     }};
 }
 
+/// Implements [`BuildStr`] for a trait object whose trait requires [`DynBuildStr`].
+///
+/// The trait object is rendered with its concrete type, and pointers to it are coerced back to it,
+/// like `Box::new(Circle(1u8,)) as Box<dyn Shape>`.
+/// The type is written as given, so it must be nameable where the output is used.
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+/// use buildstr::{BuildStr, DynBuildStr, Options};
+///
+/// trait Shape: DynBuildStr {}
+/// buildstr::impl_dyn_buildstr!(dyn Shape);
+///
+/// #[derive(BuildStr)]
+/// struct Square(u8);
+/// impl Shape for Square {}
+///
+/// let square: Rc<dyn Shape> = Rc::new(Square(2));
+/// assert_eq!(
+///     buildstr::render_with(&vec![square.clone(), square], &Options::default()),
+///     "{let __n0=::std::rc::Rc::new(Square(2u8,)) as ::std::rc::Rc<dyn Shape>;\
+///      ::std::vec::Vec::from_iter([::std::rc::Rc::clone(&__n0),::std::rc::Rc::clone(&__n0),])}"
+/// );
+/// ```
+#[macro_export]
+macro_rules! impl_dyn_buildstr {
+    ($($ty:tt)+) => {
+        impl $crate::BuildStr for $($ty)+ {
+            fn to_build_expr(&self) -> $crate::Expr {
+                // The type of the concrete value is erased by the coercion
+                $crate::__private::typed(false, || $crate::DynBuildStr::dyn_build_expr(self))
+            }
+
+            fn __dyn_type(&self) -> Option<&'static str> {
+                Some(stringify!($($ty)+))
+            }
+        }
+    };
+}
+
 impl_buildstr!(BuildStr);
 
+#[cfg(feature = "compress")]
+pub use compress::FromBuildBytes;
+pub use error::ErrorMessage;
+pub use expr::{Expr, Layout, MethodCall, Path, Segment, Stmt};
+pub use options::{
+    render_with, try_render_with, Instants, IntFormat, Options, Poison, References, RenderError,
+    Style, Suffixes,
+};
+pub use renderer::Renderer;
+
 pub mod __private;
-mod _std;
\ No newline at end of file
+mod _std;
+pub mod build;
+#[cfg(feature = "compress")]
+pub mod compress;
+mod error;
+mod expr;
+mod options;
+mod renderer;
\ No newline at end of file
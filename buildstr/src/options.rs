@@ -0,0 +1,491 @@
+//! Options that change how values are rendered by [`BuildStr`](crate::BuildStr).
+//!
+//! Calling `to_build_string` directly always uses [`Options::default`].<br>
+//! To render with different options, use [`render_with`].
+
+use crate::BuildStr;
+
+/// Error found while rendering a value.
+///
+/// `to_build_string` and [`render_with`] panic with it, use [`try_render_with`] to handle it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderError {
+    /// The value contains itself, so it can't be rebuilt.
+    ///
+    /// `path` contains the types from the value that closes the cycle until its inner occurrence.
+    Cycle { path: Vec<&'static str> },
+    /// The value is nested more than [`Options::max_depth`] times.
+    ///
+    /// `path` contains the types from the outermost value until the one that exceeded the limit.
+    DepthLimit {
+        limit: usize,
+        path: Vec<&'static str>,
+    },
+    /// A lock (`Mutex` or `RwLock`) is poisoned, and [`Options::poison`] is [`Poison::Fail`].
+    ///
+    /// `path` contains the types from the outermost value until the lock.
+    Poisoned { path: Vec<&'static str> },
+    /// A lock (`Mutex` or `RwLock`) is held while rendering it, so its value can't be read.
+    ///
+    /// The lock may be held by another thread, so values with locks shared between threads
+    /// should be rendered with [`try_render_with`].
+    ///
+    /// `path` contains the types from the outermost value until the lock.
+    Locked { path: Vec<&'static str> },
+    /// An `Instant` was found, and [`Options::instants`] is [`Instants::Fail`].
+    ///
+    /// `path` contains the types from the outermost value until the instant.
+    Instant { path: Vec<&'static str> },
+    /// A `dyn Any` was found, and its type isn't registered in the [`Renderer`](crate::Renderer).
+    ///
+    /// `path` contains the types from the outermost value until the trait object.
+    Unregistered { path: Vec<&'static str> },
+    /// A stdio of a `Command` is redirected to a file or a handle, which can't be rebuilt.
+    ///
+    /// `path` contains the types from the outermost value until the command.
+    Redirected { path: Vec<&'static str> },
+    /// A reference contains a shared or deduplicated value, and [`Options::references`] is [`References::Leak`].
+    /// The value is bound to a variable, which can't be moved to the leaked box.
+    ///
+    /// `path` contains the types from the outermost value until the one that holds the reference.
+    Leaked { path: Vec<&'static str> },
+    /// A value is rebuilt from its kind or from its `Debug` or `Display`, like the `env_clear` and the stdio
+    /// of a `Command` or the message of an `AddrParseError`, but they aren't recognized, as a newer `std` may
+    /// add kinds or change the text.
+    ///
+    /// `path` contains the types from the outermost value until the one that isn't recognized.
+    Unrecognized { path: Vec<&'static str> },
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn path(f: &mut std::fmt::Formatter<'_>, path: &[&str]) -> std::fmt::Result {
+            // Deep paths are shortened, only the start and the end are relevant
+            if path.len() > 8 {
+                let (start, end) = (&path[..4], &path[path.len() - 4..]);
+                write!(f, "{} -> ... -> {}", start.join(" -> "), end.join(" -> "))
+            } else {
+                write!(f, "{}", path.join(" -> "))
+            }
+        }
+        match self {
+            RenderError::Cycle { path: p } => {
+                write!(f, "cycle found while rendering: ")?;
+                path(f, p)
+            }
+            RenderError::DepthLimit { limit, path: p } => {
+                write!(f, "maximum depth of {limit} exceeded while rendering: ")?;
+                path(f, p)
+            }
+            RenderError::Poisoned { path: p } => {
+                write!(f, "poisoned lock found while rendering: ")?;
+                path(f, p)
+            }
+            RenderError::Locked { path: p } => {
+                write!(f, "held lock found while rendering: ")?;
+                path(f, p)
+            }
+            RenderError::Instant { path: p } => {
+                write!(f, "instant found while rendering: ")?;
+                path(f, p)
+            }
+            RenderError::Unregistered { path: p } => {
+                write!(
+                    f,
+                    "unregistered type behind `dyn Any` found while rendering: "
+                )?;
+                path(f, p)
+            }
+            RenderError::Redirected { path: p } => {
+                write!(f, "redirected stdio found while rendering: ")?;
+                path(f, p)
+            }
+            RenderError::Leaked { path: p } => {
+                write!(
+                    f,
+                    "shared value behind a leaked reference found while rendering: "
+                )?;
+                path(f, p)
+            }
+            RenderError::Unrecognized { path: p } => {
+                write!(f, "unrecognized kind or text found while rendering: ")?;
+                path(f, p)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+/// Forms used for the builders of the standard types, see [`Options::style`].
+///
+/// # Examples
+/// ```
+/// use buildstr::{Options, Style};
+///
+/// let value = (vec![Some(1u8)], 1u8..=4u8);
+/// let render = |style| {
+///     let mut options = Options::default();
+///     options.style = style;
+///     buildstr::render_with(&value, &options)
+/// };
+/// assert_eq!(
+///     render(Style::Canonical),
+///     "(::std::vec::Vec::from_iter([::core::option::Option::Some(1u8),]), ::core::ops::RangeInclusive::new(1u8,4u8))"
+/// );
+/// assert_eq!(render(Style::Idiomatic), "(vec![Some(1u8)], 1u8..=4u8)");
+/// assert_eq!(render(Style::Minimal), "(vec![Some(1u8),],1u8..=4u8)");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Style {
+    /// Fully qualified paths and constructors, like `::std::vec::Vec::from_iter([1u8,])`,
+    /// so the output doesn't depend on the names in scope.
+    #[default]
+    Canonical,
+    /// The forms a person would write, like `vec![1u8]`, `1u8..=4u8`, `Some(1u8)` and `String::from("text")`,
+    /// formatted like `rustfmt` would. The names of the prelude must be in scope.
+    Idiomatic,
+    /// The same forms as [`Idiomatic`](Style::Idiomatic), without any unnecessary whitespace.
+    Minimal,
+}
+
+/// Where the type suffixes of number literals are written, see [`Options::suffixes`].
+///
+/// # Examples
+/// ```
+/// use buildstr::{BuildStr, Options, Suffixes};
+///
+/// #[derive(BuildStr)]
+/// struct Pixel {
+///     rgb: [u8; 3],
+/// }
+///
+/// let pixels = [Pixel { rgb: [255, 0, 0] }];
+/// let render = |suffixes| {
+///     let mut options = Options::default();
+///     options.suffixes = suffixes;
+///     buildstr::render_with(&pixels, &options)
+/// };
+/// assert_eq!(render(Suffixes::Always), "[Pixel{rgb: [255u8,0u8,0u8,],},]");
+/// assert_eq!(render(Suffixes::Inferred), "[Pixel{rgb: [255,0,0,],},]");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Suffixes {
+    /// Suffix every number, like `30u8`.
+    #[default]
+    Always,
+    /// Omit the suffixes where the type is fixed by the enclosing value: in the fields of derived types
+    /// (except the ones of a generic type), in the literals of the elements of arrays and ordered collections
+    /// whose suffix is written at the same position in an earlier element, and in the values nested in them.
+    Inferred,
+    /// Same as [`Inferred`](Suffixes::Inferred), for a value whose own type is fixed too,
+    /// like the initializer of a typed `static`.
+    InferredTyped,
+}
+
+/// How references to values are written, see [`Options::references`].
+///
+/// # Examples
+/// ```
+/// use buildstr::{BuildStr, Options, References};
+///
+/// #[derive(BuildStr)]
+/// struct Config {
+///     name: &'static String,
+///     retries: &'static u8,
+/// }
+///
+/// let config = Config { name: Box::leak(Box::new("main".into())), retries: &3 };
+/// let mut options = Options::default();
+/// options.references = References::Leak;
+/// assert_eq!(
+///     buildstr::render_with(&config, &options),
+///     "Config{name: ::std::boxed::Box::leak(::std::boxed::Box::new(::std::string::String::from(\"main\"))),retries: &3u8,}"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum References {
+    /// Borrow the value, like `&value`, so the reference only lives until the end of the enclosing statement
+    /// (or the enclosing block if it's bound with `let`), unless the value is a constant.
+    #[default]
+    Borrow,
+    /// Move the values that aren't constants to the heap and leak them, like
+    /// `::std::boxed::Box::leak(::std::boxed::Box::new(value))`, so their references are `'static`.
+    ///
+    /// The values are never freed, so it should only be used for values that live until the program ends.
+    /// Constants, like `&1u8` or `&[1u8, 2u8]`, are already `'static` and keep their form.
+    ///
+    /// `Box::leak` can't be called in constants, so the output is only valid at runtime,
+    /// like inside a `LazyLock` instead of a `static` item.
+    /// References to shared and deduplicated values can't be leaked, and fail with [`RenderError::Leaked`].
+    Leak,
+}
+
+/// How poisoned locks are rendered, see [`Options::poison`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Poison {
+    /// Render the value of the lock, rebuilding a lock that isn't poisoned, as if `clear_poison` was called.
+    #[default]
+    Clear,
+    /// Fail with [`RenderError::Poisoned`].
+    Fail,
+}
+
+/// How `Instant`s are rendered, see [`Options::instants`].
+///
+/// An `Instant` has no absolute representation, it can only be compared with other instants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Instants {
+    /// Relative to the moment the value is rebuilt, keeping the distance from the moment it was rendered,
+    /// like `::std::time::Instant::now() - ::core::time::Duration::new(5, 0)` for an instant 5 seconds in the past.
+    ///
+    /// All the instants of a value are measured from the same moment, so the distances between them are kept,
+    /// except for the time the rebuilt code takes between its calls to `Instant::now()`.
+    #[default]
+    Relative,
+    /// Fail with [`RenderError::Instant`].
+    Fail,
+}
+
+/// Base used to write integer literals, see [`Options::int_format`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntFormat {
+    /// Like `255u8`.
+    #[default]
+    Decimal,
+    /// Like `0xff_u8`.
+    Hex,
+    /// Like `0b11111111_u8`.
+    Binary,
+}
+
+/// Options used while rendering a value.
+///
+/// New options may be added in any release, so they are built from [`Options::default`]
+/// and changed field by field.
+///
+/// # Examples
+/// ```
+/// use buildstr::{BuildStr, Options};
+///
+/// let mut options = Options::default();
+/// options.deterministic = false;
+/// let set = ::std::collections::HashSet::from([1]);
+/// assert!(buildstr::render_with(&set, &options).contains("set.extend([1i32,])"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+#[non_exhaustive]
+pub struct Options {
+    /// Sort the entries of unordered collections (`HashMap`, `HashSet`) by their rendered value,
+    /// so the output is the same across runs and machines.
+    ///
+    /// Enabled by default.
+    pub deterministic: bool,
+    /// Render every shared pointer (`Rc`) that is seen more than once as a single `let` binding,
+    /// cloned at each use, so the sharing is preserved after rebuilding the value.
+    ///
+    /// A weak pointer to a value that contains it is rebuilt with `new_cyclic`.
+    ///
+    /// Only used by [`render_with`], enabled by default.
+    pub share_pointers: bool,
+    /// Maximum number of nested values (derived types and cells) before failing with [`RenderError::DepthLimit`].
+    ///
+    /// Disabled by default, since the plain [`BuildStr::to_build_string`] panics on any [`RenderError`];
+    /// use it with [`try_render_with`] to turn a stack overflow on deeply nested values into an error.
+    pub max_depth: Option<usize>,
+    /// Minimum length of the subexpressions (fields, elements and entries of collections) that are deduplicated.
+    ///
+    /// Subexpressions that are repeated are declared once in a `let` binding, and cloned at each use,
+    /// so their types must implement `Clone`. Subexpressions that contain references are never deduplicated.
+    ///
+    /// Only used by [`render_with`], disabled by default.
+    pub dedup: Option<usize>,
+    /// Rules replacing the prefix of the rendered paths, like `("crate::model", "::model_types")`,
+    /// applied in order with [`Expr::rewrite_prefix`](crate::Expr::rewrite_prefix).
+    ///
+    /// They apply to every path, including the ones in types, so the output can be used from another crate.
+    ///
+    /// Only used by [`render_with`], empty by default.
+    pub rewrite_paths: Vec<(String, String)>,
+    /// Import the types of the absolute paths with `use` statements, and refer to them by name,
+    /// see [`Expr::hoist_uses`](crate::Expr::hoist_uses).
+    ///
+    /// Applied after [`rewrite_paths`](Self::rewrite_paths). Only used by [`render_with`], disabled by default.
+    pub hoist_uses: bool,
+    /// Render derived types with their path from the crate root, like `crate::model::Point`,
+    /// instead of their name.
+    ///
+    /// Types defined inside functions can't be named from their module, so they keep their name.
+    ///
+    /// Disabled by default.
+    pub qualify_types: bool,
+    /// Forms used for the builders of the standard types, and the formatting of the output.
+    ///
+    /// The formatting is only applied by [`render_with`], defaults to [`Style::Canonical`].
+    pub style: Style,
+    /// Where the type suffixes of number literals are written.
+    ///
+    /// Only used by [`render_with`], defaults to [`Suffixes::Always`].
+    pub suffixes: Suffixes,
+    /// Base used to write integer literals.
+    ///
+    /// Defaults to [`IntFormat::Decimal`].
+    pub int_format: IntFormat,
+    /// Separate the digits of integer literals with `_`, in groups of 3 for decimal and 4 for the other bases,
+    /// like `1_000_000u32` or `0xffff_ffff_u32`.
+    ///
+    /// Disabled by default.
+    pub digit_separators: bool,
+    /// Minimum length of the arrays, slices and vectors whose elements are all equal that are written
+    /// repeating the element, like `[0u8; 4096]`.
+    ///
+    /// Elements that only contain literals are repeated with `[expr; N]` or `vec![expr; N]`,
+    /// other elements are built once for each position, like with `::core::array::from_fn`.
+    ///
+    /// Disabled by default.
+    pub repeat: Option<usize>,
+    /// Minimum length of the runs of equal elements that are repeated in arrays and vectors
+    /// with other elements, which are then built by chaining iterators.
+    ///
+    /// Disabled by default.
+    pub run_length: Option<usize>,
+    /// Maximum number of elements written in a single array expression by `Vec`, `VecDeque`, `BTreeMap` and `HashMap`.
+    ///
+    /// Larger collections are built from several chunks, so `rustc` doesn't check a huge expression at once.
+    /// Chunks of primitive types, like `(u32, char)`, are declared as `const` items.
+    /// Other chunks are still checked together with the rest of the function they are built in,
+    /// so chunking only helps collections of primitive types.
+    ///
+    /// Disabled by default.
+    pub chunk_size: Option<usize>,
+    /// How references (`&T`, `&mut T` and slices) are written, so they can be stored where `&'static T` is required.
+    ///
+    /// Defaults to [`References::Borrow`].
+    pub references: References,
+    /// How poisoned locks (`Mutex` and `RwLock`) are rendered.
+    ///
+    /// Defaults to [`Poison::Clear`].
+    pub poison: Poison,
+    /// Ordering used to load the value of atomics, like `AtomicU32`.
+    ///
+    /// `Release` and `AcqRel` only apply to stores, so they load with `Relaxed` and `Acquire`.
+    ///
+    /// Defaults to [`Ordering::Relaxed`](std::sync::atomic::Ordering::Relaxed).
+    pub atomic_ordering: std::sync::atomic::Ordering,
+    /// How `Instant`s are rendered.
+    ///
+    /// Defaults to [`Instants::Relative`].
+    pub instants: Instants,
+    /// Write the sequences of bytes (`[u8; N]`, `&[u8]`, `Vec<u8>`, `CStr` and `CString`) with byte string literals,
+    /// like `b"text\0"`, instead of a list of numbers.
+    ///
+    /// Sequences that are written more compactly with [`repeat`](Self::repeat) or [`run_length`](Self::run_length)
+    /// keep using them. Disabled by default.
+    pub byte_strings: bool,
+    /// Minimum length in bytes of the byte sequences and strings that are written to sidecar files,
+    /// and included with `include_bytes!` or `include_str!`.
+    ///
+    /// Only used by [`build::write_with`](crate::build::write_with), defaults to `Some(4096)`.
+    pub externalize: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            deterministic: true,
+            share_pointers: true,
+            max_depth: None,
+            dedup: None,
+            rewrite_paths: Vec::new(),
+            hoist_uses: false,
+            qualify_types: false,
+            style: Style::Canonical,
+            suffixes: Suffixes::Always,
+            int_format: IntFormat::Decimal,
+            digit_separators: false,
+            repeat: None,
+            run_length: None,
+            chunk_size: None,
+            references: References::Borrow,
+            poison: Poison::Clear,
+            atomic_ordering: std::sync::atomic::Ordering::Relaxed,
+            instants: Instants::Relative,
+            byte_strings: false,
+            externalize: Some(4096),
+        }
+    }
+}
+
+impl Options {
+    /// Applies the options that transform the whole output to a rendered value:
+    /// [`rewrite_paths`](Self::rewrite_paths), [`hoist_uses`](Self::hoist_uses) and the formatting of the [`style`](Self::style).
+    pub(crate) fn transform(&self, mut expr: crate::Expr) -> String {
+        for (from, to) in &self.rewrite_paths {
+            expr.rewrite_prefix(from, to);
+        }
+        if self.hoist_uses {
+            expr.hoist_uses();
+        }
+        match self.style {
+            Style::Canonical => expr.to_string(),
+            Style::Idiomatic => expr.to_pretty_string(),
+            Style::Minimal => crate::expr::compact(&expr),
+        }
+    }
+}
+
+/// Gets the builder string of `value`, rendered with the specified [`Options`].
+///
+/// # Examples
+/// ```
+/// use buildstr::{BuildStr, Options};
+///
+/// let map = ::std::collections::HashMap::from([("b", 2), ("a", 1)]);
+/// assert!(buildstr::render_with(&map, &Options::default()).contains("map.extend([(\"a\",1i32),(\"b\",2i32),])"));
+///
+/// let shared = ::std::rc::Rc::new(1u8);
+/// assert_eq!(
+///     buildstr::render_with(&(shared.clone(), shared), &Options::default()),
+///     "{let __n0=::std::rc::Rc::new(1u8);(::std::rc::Rc::clone(&__n0), ::std::rc::Rc::clone(&__n0))}"
+/// );
+/// ```
+///
+/// # Panics
+/// Panics if the value can't be rendered, see [`try_render_with`].
+#[must_use]
+pub fn render_with<T: BuildStr + ?Sized>(value: &T, options: &Options) -> String {
+    try_render_with(value, options).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Same as [`render_with`], but returns an error if the value can't be rendered.
+///
+/// # Errors
+/// Returns a [`RenderError`] if the value contains a cycle that can't be rebuilt,
+/// is nested more than [`Options::max_depth`] times, or contains a lock that can't be read.
+///
+/// # Examples
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+/// use buildstr::{BuildStr, Options, RenderError};
+///
+/// #[derive(BuildStr)]
+/// struct Node {
+///     next: Option<Rc<RefCell<Node>>>,
+/// }
+///
+/// let node = Rc::new(RefCell::new(Node { next: None }));
+/// node.borrow_mut().next = Some(node.clone());
+///
+/// let error = buildstr::try_render_with(&node, &Options::default()).unwrap_err();
+/// assert!(matches!(error, RenderError::Cycle { .. }));
+/// # node.borrow_mut().next = None;
+/// ```
+pub fn try_render_with<T: BuildStr + ?Sized>(
+    value: &T,
+    options: &Options,
+) -> Result<String, RenderError> {
+    let guard = crate::__private::context::enter(options.clone());
+    let expr = value.to_build_expr();
+    guard.finish(expr)
+}
@@ -0,0 +1,169 @@
+//! Rendering with per-type overrides, see [`Renderer`].
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::error::Error;
+use std::rc::Rc;
+
+use crate::{BuildStr, Options, RenderError};
+
+pub(crate) type Override = Rc<dyn Fn(&dyn Any) -> String>;
+/// Casts an error to its concrete type, if it's the registered one, see [`Renderer::register_error`].
+pub(crate) type ErrorCast = for<'a> fn(&'a (dyn Error + 'static)) -> Option<&'a dyn Any>;
+
+#[derive(Clone, Default)]
+pub(crate) struct Overrides {
+    pub(crate) types: HashMap<TypeId, Override>,
+    pub(crate) errors: Vec<ErrorCast>,
+}
+
+/// Wraps `f` to receive any value, only called with values of type `T`.
+fn erase<T: 'static>(f: impl Fn(&T) -> String + 'static) -> Override {
+    Rc::new(move |value: &dyn Any| f(value.downcast_ref().expect("overrides are keyed by type")))
+}
+
+fn cast_error<'a, E: Error + 'static>(error: &'a (dyn Error + 'static)) -> Option<&'a dyn Any> {
+    error.downcast_ref::<E>().map(|e| e as &dyn Any)
+}
+
+/// Renders values with [`Options`] and custom builders for some types.
+///
+/// Overrides are used for every value whose type is `'static` (it has no lifetimes or generic parameters),
+/// wherever it's nested, like the fields of derived types or the elements of a `Vec<String>`.<br>
+/// Generic types like `Vec<T>` themselves can't be overridden.
+///
+/// # Examples
+/// ```
+/// use buildstr::{BuildStr, Renderer};
+///
+/// #[derive(BuildStr)]
+/// struct Config {
+///     name: String,
+///     retries: u8,
+/// }
+///
+/// let mut renderer = Renderer::default();
+/// renderer.register(|s: &String| format!("{s:?}.into()"));
+///
+/// let config = Config { name: "main".into(), retries: 3 };
+/// assert_eq!(renderer.render(&config), "Config{name: \"main\".into(),retries: 3u8,}");
+///
+/// renderer.register(|_: &Config| "CONFIG".into());
+/// assert_eq!(renderer.render(&vec![config]), "::std::vec::Vec::from_iter([CONFIG,])");
+/// ```
+#[derive(Clone, Default)]
+pub struct Renderer {
+    pub options: Options,
+    overrides: Overrides,
+}
+
+impl Renderer {
+    /// Creates a renderer with the specified options and no overrides.
+    #[must_use]
+    pub fn new(options: Options) -> Self {
+        Self {
+            options,
+            overrides: Overrides::default(),
+        }
+    }
+
+    /// Renders the values of type `T` with `f`, replacing the previous override of `T`.
+    ///
+    /// While `f` runs, values of type `T` are rendered with their [`BuildStr`] implementation.
+    pub fn register<T: 'static>(&mut self, f: impl Fn(&T) -> String + 'static) -> &mut Self {
+        self.overrides.types.insert(TypeId::of::<T>(), erase(f));
+        self
+    }
+
+    /// Renders the values of type `T` behind a `dyn Any` (like `Box<dyn Any>`) with their [`BuildStr`] implementation.
+    ///
+    /// A `dyn Any` can only be rendered if the type of its value is registered, with this or [`register`](Self::register).
+    ///
+    /// # Examples
+    /// ```
+    /// use std::any::Any;
+    /// use buildstr::Renderer;
+    ///
+    /// let value: Box<dyn Any> = Box::new(5u8);
+    /// let mut renderer = Renderer::default();
+    /// assert!(renderer.try_render(&value).is_err());
+    /// renderer.register_any::<u8>();
+    /// assert_eq!(renderer.render(&value), "Box::new(5u8) as Box<dyn ::std::any::Any>");
+    /// ```
+    pub fn register_any<T: BuildStr + 'static>(&mut self) -> &mut Self {
+        self.register(|value: &T| value.to_build_string())
+    }
+
+    /// Renders the errors of type `E` behind a `dyn Error` (like `Box<dyn Error + Send + Sync>`)
+    /// with their [`BuildStr`] implementation, instead of an [`ErrorMessage`](crate::ErrorMessage).
+    ///
+    /// The errors of `std` are always rendered with their type.
+    /// `E` must be [`Send`] and [`Sync`], since a registered error can be rebuilt as the source of an
+    /// [`ErrorMessage`](crate::ErrorMessage), which keeps its sources as `Box<dyn Error + Send + Sync>`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::error::Error;
+    /// use buildstr::{BuildStr, Renderer};
+    ///
+    /// #[derive(Debug, BuildStr)]
+    /// struct Timeout(u16);
+    /// impl std::fmt::Display for Timeout {
+    ///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "timed out after {}s", self.0)
+    ///     }
+    /// }
+    /// impl Error for Timeout {}
+    ///
+    /// let error: Box<dyn Error + Send + Sync> = Box::new(Timeout(30));
+    /// let mut renderer = Renderer::default();
+    /// assert_eq!(
+    ///     renderer.render(&error),
+    ///     "Box::new(::buildstr::ErrorMessage::new(\"timed out after 30s\")) as Box<dyn ::std::error::Error + Send + Sync>"
+    /// );
+    /// renderer.register_error::<Timeout>();
+    /// assert_eq!(
+    ///     renderer.render(&error),
+    ///     "Box::new(Timeout(30u16,)) as Box<dyn ::std::error::Error + Send + Sync>"
+    /// );
+    /// ```
+    pub fn register_error<E: Error + BuildStr + Send + Sync + 'static>(&mut self) -> &mut Self {
+        self.overrides.errors.push(cast_error::<E>);
+        self.register_any::<E>()
+    }
+
+    /// Removes the override of `T`, returning `true` if it existed.
+    pub fn unregister<T: 'static>(&mut self) -> bool {
+        self.overrides.types.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// Gets the builder string of `value`.
+    ///
+    /// # Panics
+    /// Panics if the value can't be rendered, see [`try_render`](Self::try_render).
+    #[must_use]
+    pub fn render<T: BuildStr + 'static>(&self, value: &T) -> String {
+        self.try_render(value).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Same as [`render`](Self::render), but returns an error if the value can't be rendered.
+    ///
+    /// # Errors
+    /// Returns a [`RenderError`] if the value contains a cycle that can't be rebuilt,
+    /// is nested more than [`Options::max_depth`] times, or contains a lock that can't be read.
+    pub fn try_render<T: BuildStr + 'static>(&self, value: &T) -> Result<String, RenderError> {
+        let guard =
+            crate::__private::context::enter_with(self.options.clone(), self.overrides.clone());
+        let expr = crate::__private::render_static(value, || value.to_build_expr());
+        guard.finish(expr)
+    }
+}
+
+impl std::fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Renderer")
+            .field("options", &self.options)
+            .field("overrides", &self.overrides.types.len())
+            .finish()
+    }
+}
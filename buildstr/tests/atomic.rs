@@ -0,0 +1,71 @@
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicUsize, Ordering};
+
+use buildstr::{BuildStr, Options};
+
+#[test]
+fn atomics() {
+    assert_eq!(
+        AtomicBool::new(true).to_build_string(),
+        "::core::sync::atomic::AtomicBool::new(true)"
+    );
+    assert_eq!(
+        AtomicU32::new(7).to_build_string(),
+        "::core::sync::atomic::AtomicU32::new(7u32)"
+    );
+    assert_eq!(
+        AtomicI64::new(-3).to_build_string(),
+        "::core::sync::atomic::AtomicI64::new(-3i64)"
+    );
+    assert_eq!(
+        Ordering::SeqCst.to_build_string(),
+        "::core::sync::atomic::Ordering::SeqCst"
+    );
+}
+
+/// Asserts that `value` is rendered as `builder`, and compiles that builder as the initializer of a `static`.
+macro_rules! static_builder {
+    ($value:expr => $ty:ty = $($builder:tt)*) => {{
+        assert_eq!($value.to_build_string(), stringify!($($builder)*));
+        static VALUE: $ty = $($builder)*;
+        &VALUE
+    }};
+}
+
+#[test]
+fn statics() {
+    // `new` is const, so they can initialize statics
+    let counter = AtomicU32::new(7);
+    let rebuilt =
+        static_builder!(counter => AtomicU32 = ::core::sync::atomic::AtomicU32::new(7u32));
+    assert_eq!(rebuilt.load(Ordering::Relaxed), 7);
+}
+
+#[test]
+fn snapshot() {
+    #[derive(BuildStr)]
+    struct Stats {
+        requests: AtomicUsize,
+        enabled: AtomicBool,
+    }
+
+    let stats = Stats {
+        requests: AtomicUsize::new(0),
+        enabled: AtomicBool::new(false),
+    };
+    stats.requests.fetch_add(2, Ordering::SeqCst);
+    stats.enabled.store(true, Ordering::SeqCst);
+    assert_eq!(
+        stats.to_build_string(),
+        "Stats{requests: ::core::sync::atomic::AtomicUsize::new(2usize),enabled: ::core::sync::atomic::AtomicBool::new(true),}"
+    );
+
+    // Store-only orderings are turned into load orderings
+    for ordering in [Ordering::SeqCst, Ordering::Release, Ordering::AcqRel] {
+        let mut options = Options::default();
+        options.atomic_ordering = ordering;
+        assert_eq!(
+            buildstr::render_with(&stats.requests, &options),
+            "::core::sync::atomic::AtomicUsize::new(2usize)"
+        );
+    }
+}
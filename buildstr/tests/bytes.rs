@@ -0,0 +1,98 @@
+use buildstr::{BuildStr, Options, Style};
+
+fn options() -> Options {
+    let mut options = Options::default();
+    options.byte_strings = true;
+    options
+}
+
+#[test]
+fn byte_strings() {
+    let options = options();
+    let data = *b"GIF89a\0\x01\n\"\\\xff";
+    assert_eq!(
+        buildstr::render_with(&data, &options),
+        r#"*b"GIF89a\0\x01\n\"\\\xff""#
+    );
+    assert_eq!(*b"GIF89a\0\x01\n\"\\\xff", data);
+    assert_eq!(
+        buildstr::render_with(&&data[..], &options),
+        r#"b"GIF89a\0\x01\n\"\\\xff""#
+    );
+    assert_eq!(
+        buildstr::render_with(&data.to_vec(), &options),
+        r#"::std::vec::Vec::from(b"GIF89a\0\x01\n\"\\\xff")"#
+    );
+    assert_eq!(
+        ::std::vec::Vec::from(b"GIF89a\0\x01\n\"\\\xff"),
+        data.to_vec()
+    );
+    assert_eq!(
+        buildstr::render_with(&std::ffi::CString::new("ab").unwrap(), &options),
+        "::std::ffi::CString::new(*b\"ab\").unwrap()"
+    );
+    assert_eq!(::std::ffi::CString::new(*b"ab").unwrap().as_bytes(), b"ab");
+
+    let mut idiomatic = options.clone();
+    idiomatic.style = Style::Idiomatic;
+    assert_eq!(
+        buildstr::render_with(&b"abc".to_vec(), &idiomatic),
+        "b\"abc\".to_vec()"
+    );
+
+    // Empty and mutable sequences keep their form
+    assert_eq!(
+        buildstr::render_with(&Vec::<u8>::new(), &options),
+        "::std::vec::Vec::from_iter([])"
+    );
+    let slice = &mut [1u8][..];
+    assert_eq!(buildstr::render_with(&slice, &options), "&mut [1u8,]");
+
+    // Disabled by default
+    assert_eq!([1u8, 2u8].to_build_string(), "[1u8,2u8,]");
+    assert_eq!(
+        std::ffi::CString::new("ab").unwrap().to_build_string(),
+        "::std::ffi::CString::new([97u8,98u8,]).unwrap()"
+    );
+}
+
+#[test]
+fn strings() {
+    assert_eq!(
+        String::from("a\\b").to_build_string(),
+        r#"::std::string::String::from("a\\b")"#
+    );
+}
+
+#[test]
+fn externalize() {
+    let dir = std::env::temp_dir().join(format!("buildstr-bytes-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::env::set_var("OUT_DIR", &dir);
+
+    let data: Vec<u8> = (0..=255u8).collect();
+    let text = "text ".repeat(10);
+    let mut options = options();
+    options.externalize = Some(32);
+    let value = (data.clone(), text.as_str(), data.clone(), b"small".to_vec());
+    let path = buildstr::build::write_with(&value, "value.rs", &options).unwrap();
+    assert_eq!(path, dir.join("value.rs"));
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        concat!(
+            "(::std::vec::Vec::from(include_bytes!(concat!(env!(\"OUT_DIR\"), \"/value_blob_0.bin\"))), ",
+            "include_str!(concat!(env!(\"OUT_DIR\"), \"/value_blob_1.txt\")), ",
+            "::std::vec::Vec::from(include_bytes!(concat!(env!(\"OUT_DIR\"), \"/value_blob_0.bin\"))), ",
+            "::std::vec::Vec::from(b\"small\"))"
+        )
+    );
+    assert_eq!(std::fs::read(dir.join("value_blob_0.bin")).unwrap(), data);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("value_blob_1.txt")).unwrap(),
+        text
+    );
+
+    // Large data is only externalized by the build helper
+    assert!(buildstr::render_with(&value, &options).contains("b\"\\0\\x01\\x02"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,96 @@
+use std::error::Error;
+use std::fmt;
+
+use buildstr::{BuildStr, ErrorMessage, Renderer};
+
+#[derive(Debug)]
+struct Config {
+    source: std::num::ParseIntError,
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid config")
+    }
+}
+
+impl Error for Config {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[derive(Debug, BuildStr)]
+struct Timeout(u16);
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {}s", self.0)
+    }
+}
+
+impl Error for Timeout {}
+
+/// Gets the messages of an error and its sources.
+fn messages(error: &(dyn Error + 'static)) -> Vec<String> {
+    let mut messages = vec![error.to_string()];
+    let mut source = error.source();
+    while let Some(error) = source {
+        messages.push(error.to_string());
+        source = error.source();
+    }
+    messages
+}
+
+#[test]
+fn chain() {
+    let error: Box<dyn Error + Send + Sync> = Box::new(Config {
+        source: "x".parse::<u8>().unwrap_err(),
+    });
+    assert_eq!(
+        error.to_build_string(),
+        "Box::new(::buildstr::ErrorMessage::with_source(\"invalid config\", <::std::primitive::u8 as ::std::str::FromStr>::from_str(\"a\").unwrap_err())) as Box<dyn ::std::error::Error + Send + Sync>"
+    );
+    let rebuilt = Box::new(::buildstr::ErrorMessage::with_source(
+        "invalid config",
+        <::std::primitive::u8 as ::std::str::FromStr>::from_str("a").unwrap_err(),
+    )) as Box<dyn ::std::error::Error + Send + Sync>;
+    assert_eq!(messages(rebuilt.as_ref()), messages(error.as_ref()));
+    assert!(rebuilt
+        .source()
+        .unwrap()
+        .downcast_ref::<std::num::ParseIntError>()
+        .is_some());
+}
+
+#[test]
+fn registered() {
+    let error: Box<dyn Error> = Box::new(ErrorMessage::with_source("request failed", Timeout(5)));
+    assert_eq!(
+        error.to_build_string(),
+        "Box::new(::buildstr::ErrorMessage::with_source(\"request failed\", ::buildstr::ErrorMessage::new(\"timed out after 5s\"))) as Box<dyn ::std::error::Error>"
+    );
+
+    let mut renderer = Renderer::default();
+    renderer.register_error::<Timeout>();
+    assert_eq!(
+        renderer.render(&error),
+        "Box::new(::buildstr::ErrorMessage::with_source(\"request failed\", Timeout(5u16,))) as Box<dyn ::std::error::Error>"
+    );
+    let rebuilt = Box::new(::buildstr::ErrorMessage::with_source(
+        "request failed",
+        Timeout(5u16),
+    )) as Box<dyn ::std::error::Error>;
+    assert_eq!(messages(rebuilt.as_ref()), messages(error.as_ref()));
+}
+
+#[test]
+fn io() {
+    let error: Box<dyn Error + Send + Sync> = Box::new(std::io::Error::other(Timeout(1)));
+    let mut renderer = Renderer::default();
+    renderer.register_error::<Timeout>();
+    assert_eq!(
+        renderer.render(&error),
+        "Box::new(::std::io::Error::new(::std::io::ErrorKind::Other, \"timed out after 1s\")) as Box<dyn ::std::error::Error + Send + Sync>"
+    );
+}
@@ -0,0 +1,118 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use buildstr::{Options, Suffixes};
+
+fn options(chunk_size: usize) -> Options {
+    let mut options = Options::default();
+    options.chunk_size = Some(chunk_size);
+    options
+}
+
+#[test]
+fn constants() {
+    let values: Vec<u32> = (1..=5).collect();
+    assert_eq!(
+        buildstr::render_with(&values, &options(2)),
+        "{const __CHUNK0:[u32; 2]=[1u32,2u32,];const __CHUNK1:[u32; 2]=[3u32,4u32,];const __CHUNK2:[u32; 1]=[5u32,];let mut items=::std::vec::Vec::with_capacity(5);items.extend_from_slice(&__CHUNK0);items.extend_from_slice(&__CHUNK1);items.extend_from_slice(&__CHUNK2);items}"
+    );
+    let rebuilt = {
+        const __CHUNK0: [u32; 2] = [1u32, 2u32];
+        const __CHUNK1: [u32; 2] = [3u32, 4u32];
+        const __CHUNK2: [u32; 1] = [5u32];
+        let mut items = ::std::vec::Vec::with_capacity(5);
+        items.extend_from_slice(&__CHUNK0);
+        items.extend_from_slice(&__CHUNK1);
+        items.extend_from_slice(&__CHUNK2);
+        items
+    };
+    assert_eq!(rebuilt, values);
+
+    // Small and repeated collections keep their form
+    assert_eq!(
+        buildstr::render_with(&values, &options(5)),
+        "::std::vec::Vec::from_iter([1u32,2u32,3u32,4u32,5u32,])"
+    );
+    let mut repeat = options(2);
+    repeat.repeat = Some(8);
+    assert_eq!(
+        buildstr::render_with(&vec![0u32; 10], &repeat),
+        "::std::vec![0u32; 10]"
+    );
+}
+
+#[test]
+fn append() {
+    let values = vec![String::from("a"), String::from("b"), String::from("c")];
+    let mut options = options(2);
+    options.suffixes = Suffixes::Inferred;
+    assert_eq!(
+        buildstr::render_with(&values, &options),
+        "{let mut items=::std::vec::Vec::with_capacity(3);items.append(&mut ::std::vec::Vec::from([::std::string::String::from(\"a\"),::std::string::String::from(\"b\"),]));items.append(&mut ::std::vec::Vec::from([::std::string::String::from(\"c\"),]));items}"
+    );
+
+    // The suffixes omitted in later chunks are inferred from the vector
+    let map = BTreeMap::from([(1u8, "a"), (2, "b"), (3, "c")]);
+    assert_eq!(
+        buildstr::render_with(&map, &options),
+        "::std::collections::BTreeMap::from_iter({let mut items=::std::vec::Vec::with_capacity(3);items.append(&mut ::std::vec::Vec::from([(1u8,\"a\"),(2,\"b\"),]));items.append(&mut ::std::vec::Vec::from([(3,\"c\"),]));items})"
+    );
+    let rebuilt = ::std::collections::BTreeMap::from_iter({
+        let mut items = ::std::vec::Vec::with_capacity(3);
+        items.append(&mut ::std::vec::Vec::from([(1u8, "a"), (2, "b")]));
+        items.append(&mut ::std::vec::Vec::from([(3, "c")]));
+        items
+    });
+    assert_eq!(rebuilt, map);
+}
+
+#[test]
+fn collections() {
+    let deque = VecDeque::from([(1u8, 'a'), (2, 'b'), (3, 'c')]);
+    assert_eq!(
+        buildstr::render_with(&deque, &options(2)),
+        "::std::collections::VecDeque::from({const __CHUNK0:[(u8, char); 2]=[(1u8, 'a'),(2u8, 'b'),];const __CHUNK1:[(u8, char); 1]=[(3u8, 'c'),];let mut items=::std::vec::Vec::with_capacity(3);items.extend_from_slice(&__CHUNK0);items.extend_from_slice(&__CHUNK1);items})"
+    );
+    let rebuilt = ::std::collections::VecDeque::from({
+        const __CHUNK0: [(u8, char); 2] = [(1u8, 'a'), (2u8, 'b')];
+        const __CHUNK1: [(u8, char); 1] = [(3u8, 'c')];
+        let mut items = ::std::vec::Vec::with_capacity(3);
+        items.extend_from_slice(&__CHUNK0);
+        items.extend_from_slice(&__CHUNK1);
+        items
+    });
+    assert_eq!(rebuilt, deque);
+
+    let map = HashMap::from([(1u8, 1.5f32), (2, 2.5), (3, 3.5)]);
+    assert_eq!(
+        buildstr::render_with(&map, &options(2)),
+        "{let mut map=::std::collections::HashMap::with_capacity_and_hasher(3,::std::collections::hash_map::RandomState::new());map.extend({const __CHUNK0:[(u8, f32); 2]=[(1u8,1.5f32),(2u8,2.5f32),];const __CHUNK1:[(u8, f32); 1]=[(3u8,3.5f32),];let mut items=::std::vec::Vec::with_capacity(3);items.extend_from_slice(&__CHUNK0);items.extend_from_slice(&__CHUNK1);items});map}"
+    );
+}
+
+#[test]
+fn inferred() {
+    // The type of the constants is read from the suffixes of the first element
+    let mut options = options(2);
+    options.suffixes = Suffixes::Inferred;
+    let values: Vec<i16> = vec![-1, 2, 3];
+    assert_eq!(
+        buildstr::render_with(&values, &options),
+        "{const __CHUNK0:[i16; 2]=[-1i16,2,];const __CHUNK1:[i16; 1]=[3,];let mut items=::std::vec::Vec::with_capacity(3);items.extend_from_slice(&__CHUNK0);items.extend_from_slice(&__CHUNK1);items}"
+    );
+    let rebuilt = {
+        const __CHUNK0: [i16; 2] = [-1i16, 2];
+        const __CHUNK1: [i16; 1] = [3];
+        let mut items = ::std::vec::Vec::with_capacity(3);
+        items.extend_from_slice(&__CHUNK0);
+        items.extend_from_slice(&__CHUNK1);
+        items
+    };
+    assert_eq!(rebuilt, values);
+
+    // Elements that aren't literals are appended
+    let values = vec![1.0f64, f64::NAN, 2.0];
+    assert_eq!(
+        buildstr::render_with(&values, &self::options(2)),
+        "{let mut items=::std::vec::Vec::with_capacity(3);items.append(&mut ::std::vec::Vec::from([1f64,::core::primitive::f64::NAN,]));items.append(&mut ::std::vec::Vec::from([2f64,]));items}"
+    );
+}
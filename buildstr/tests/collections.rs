@@ -10,3 +10,60 @@ fn btreemap() {
     ]);
     assert_eq!(map.to_build_string(), "::std::collections::BTreeMap::from_iter([(::std::string::String::from(\"a\"),1i32),(::std::string::String::from(\"b\"),2i32),(::std::string::String::from(\"c\"),3i32),(::std::string::String::from(\"d\"),4i32),])");
 }
+
+#[test]
+fn hashmap() {
+    let map = ::std::collections::HashMap::from([
+        ("d".to_string(), 4),
+        ("b".to_string(), 2),
+        ("a".to_string(), 1),
+        ("c".to_string(), 3),
+    ]);
+    assert_eq!(map.to_build_string(), "{let mut map=::std::collections::HashMap::with_capacity_and_hasher(4,::std::collections::hash_map::RandomState::new());map.extend([(::std::string::String::from(\"a\"),1i32),(::std::string::String::from(\"b\"),2i32),(::std::string::String::from(\"c\"),3i32),(::std::string::String::from(\"d\"),4i32),]);map}");
+}
+
+#[test]
+fn hashset() {
+    let set = ::std::collections::HashSet::from(["c", "a", "b"]);
+    assert_eq!(set.to_build_string(), "{let mut set=::std::collections::HashSet::with_capacity_and_hasher(3,::std::collections::hash_map::RandomState::new());set.extend([\"a\",\"b\",\"c\",]);set}");
+
+    // Without sorting, the entries keep the iteration order of the set
+    let mut options = buildstr::Options::default();
+    options.deterministic = false;
+    let entries: String = set.iter().map(|entry| format!("{entry:?},")).collect();
+    assert_eq!(
+        buildstr::render_with(&set, &options),
+        format!("{{let mut set=::std::collections::HashSet::with_capacity_and_hasher(3,::std::collections::hash_map::RandomState::new());set.extend([{entries}]);set}}")
+    );
+}
+
+#[test]
+fn custom_hasher() {
+    type Hasher = ::core::hash::BuildHasherDefault<::std::collections::hash_map::DefaultHasher>;
+
+    let map: ::std::collections::HashMap<u8, u8, Hasher> = ::std::collections::HashMap::from_iter([(1u8, 2u8)]);
+    assert_eq!(map.to_build_string(), "{let mut map=::std::collections::HashMap::with_capacity_and_hasher(1,::core::hash::BuildHasherDefault::default());map.extend([(1u8,2u8),]);map}");
+    // The hasher is inferred from the type of the map
+    let rebuilt: ::std::collections::HashMap<u8, u8, Hasher> = {
+        let mut map = ::std::collections::HashMap::with_capacity_and_hasher(1, ::core::hash::BuildHasherDefault::default());
+        map.extend([(1u8, 2u8)]);
+        map
+    };
+    assert_eq!(rebuilt, map);
+
+    let set: ::std::collections::HashSet<u8, Hasher> = ::std::collections::HashSet::default();
+    assert_eq!(set.to_build_string(), "::std::collections::HashSet::with_capacity_and_hasher(0,::core::hash::BuildHasherDefault::default())");
+    let rebuilt: ::std::collections::HashSet<u8, Hasher> = ::std::collections::HashSet::with_capacity_and_hasher(0, ::core::hash::BuildHasherDefault::default());
+    assert_eq!(rebuilt, set);
+
+    // Where it can't be inferred, the path of the hasher is registered
+    let mut renderer = buildstr::Renderer::default();
+    renderer.register(|_: &Hasher| "::core::hash::BuildHasherDefault::<::std::collections::hash_map::DefaultHasher>::default()".into());
+    assert_eq!(renderer.render(&map), "{let mut map=::std::collections::HashMap::with_capacity_and_hasher(1,::core::hash::BuildHasherDefault::<::std::collections::hash_map::DefaultHasher>::default());map.extend([(1u8,2u8),]);map}");
+    let rebuilt = {
+        let mut map = ::std::collections::HashMap::with_capacity_and_hasher(1, ::core::hash::BuildHasherDefault::<::std::collections::hash_map::DefaultHasher>::default());
+        map.extend([(1u8, 2u8)]);
+        map
+    };
+    assert_eq!(rebuilt, map);
+}
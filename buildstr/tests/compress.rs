@@ -0,0 +1,117 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+use buildstr::compress::{compress, decompress};
+use buildstr::{FromBuildBytes, Options};
+
+#[derive(FromBuildBytes, Debug, Clone, PartialEq)]
+struct Entry<T> {
+    name: String,
+    value: T,
+    tags: Vec<(char, bool)>,
+}
+
+#[derive(FromBuildBytes, Debug, Clone, PartialEq)]
+enum Shape {
+    Circle(f64),
+    Rect { w: u16, h: u16 },
+    Empty,
+}
+
+#[derive(FromBuildBytes, Debug, PartialEq)]
+struct Unit;
+
+#[test]
+fn round_trip() {
+    let entries = vec![
+        Entry {
+            name: "first".into(),
+            value: Some(Shape::Circle(1.5)),
+            tags: vec![('a', true)],
+        },
+        Entry {
+            name: String::new(),
+            value: Some(Shape::Rect { w: 3, h: 4 }),
+            tags: Vec::new(),
+        },
+        Entry {
+            name: "last".into(),
+            value: None,
+            tags: vec![('z', false); 3],
+        },
+    ];
+    assert_eq!(
+        decompress::<Vec<Entry<Option<Shape>>>>(&compress(&entries)),
+        entries
+    );
+
+    let map = BTreeMap::from([
+        (1i64, [Shape::Empty, Shape::Empty]),
+        (-1, [Shape::Circle(0.0), Shape::Empty]),
+    ]);
+    assert_eq!(
+        decompress::<BTreeMap<i64, [Shape; 2]>>(&compress(&map)),
+        map
+    );
+    assert_eq!(
+        decompress::<(Unit, usize, Result<u8, String>)>(&compress(&(
+            Unit,
+            7usize,
+            Err::<u8, _>("e".to_owned())
+        ))),
+        (Unit, 7, Err("e".into()))
+    );
+}
+
+#[test]
+fn deterministic() {
+    let map: HashMap<u32, String> = (0..100).map(|i| (i, i.to_string())).collect();
+    let other: HashMap<u32, String> = (0..100).rev().map(|i| (i, i.to_string())).collect();
+    assert_eq!(compress(&map), compress(&other));
+    assert_eq!(decompress::<HashMap<u32, String>>(&compress(&map)), map);
+}
+
+#[test]
+#[should_panic(expected = "invalid compressed data for `alloc::string::String`")]
+fn invalid() {
+    let _ = decompress::<String>(&compress(&1u8));
+}
+
+#[test]
+fn render() {
+    let words = vec![String::from("word"); 1000];
+    let rendered = buildstr::compress::render(&words);
+    assert!(
+        rendered.starts_with("::std::sync::LazyLock::new(|| ::buildstr::compress::decompress(b\"")
+    );
+    assert!(rendered.len() < 200);
+
+    let bytes = compress(&words);
+    let lazy = LazyLock::new(|| decompress::<Vec<String>>(&bytes));
+    assert_eq!(*lazy, words);
+
+    let mut options = Options::default();
+    options.style = buildstr::Style::Idiomatic;
+    options.hoist_uses = true;
+    assert!(buildstr::compress::render_with(&words, &options)
+        .starts_with("{\n    use ::std::sync::LazyLock;"));
+}
+
+#[test]
+fn build() {
+    let dir = std::env::temp_dir().join(format!("buildstr-compress-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::env::set_var("OUT_DIR", &dir);
+
+    let values: Vec<u64> = (0..10_000).map(|i| i * i).collect();
+    let mut options = Options::default();
+    options.externalize = Some(16);
+    let path = buildstr::build::write_compressed_with(&values, "values.rs", &options).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        "::std::sync::LazyLock::new(|| ::buildstr::compress::decompress(include_bytes!(concat!(env!(\"OUT_DIR\"), \"/values_blob_0.bin\"))))"
+    );
+    let bytes = std::fs::read(dir.join("values_blob_0.bin")).unwrap();
+    assert_eq!(decompress::<Vec<u64>>(&bytes), values);
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,127 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use buildstr::{BuildStr, Options, RenderError};
+
+#[derive(BuildStr)]
+struct Node {
+    value: u8,
+    next: Option<Rc<RefCell<Node>>>,
+}
+
+#[derive(BuildStr)]
+struct Parent {
+    children: Vec<Rc<Child>>,
+}
+
+#[derive(BuildStr)]
+struct Child {
+    parent: Weak<Parent>,
+}
+
+fn cyclic_node() -> Rc<RefCell<Node>> {
+    let node = Rc::new(RefCell::new(Node {
+        value: 1,
+        next: None,
+    }));
+    node.borrow_mut().next = Some(node.clone());
+    node
+}
+
+#[test]
+fn strong() {
+    let node = cyclic_node();
+    let error = buildstr::try_render_with(&node, &Options::default()).unwrap_err();
+    assert_eq!(
+        error,
+        RenderError::Cycle {
+            path: vec![
+                "alloc::rc::Rc<core::cell::RefCell<cycle::Node>>",
+                "core::cell::RefCell<cycle::Node>",
+                "cycle::Node",
+                "alloc::rc::Rc<core::cell::RefCell<cycle::Node>>",
+            ]
+        }
+    );
+    assert_eq!(error.to_string(), "cycle found while rendering: alloc::rc::Rc<core::cell::RefCell<cycle::Node>> -> core::cell::RefCell<cycle::Node> -> cycle::Node -> alloc::rc::Rc<core::cell::RefCell<cycle::Node>>");
+    node.borrow_mut().next = None;
+}
+
+#[test]
+#[should_panic(expected = "cycle found while rendering")]
+fn strong_without_pass() {
+    let _ = cyclic_node().to_build_string();
+}
+
+#[test]
+fn weak() {
+    let parent = Rc::new_cyclic(|parent| Parent {
+        children: vec![
+            Rc::new(Child {
+                parent: parent.clone(),
+            }),
+            Rc::new(Child {
+                parent: parent.clone(),
+            }),
+        ],
+    });
+    assert_eq!(
+        buildstr::render_with(&parent, &Options::default()),
+        "::std::rc::Rc::new_cyclic(|__w0| Parent{children: ::std::vec::Vec::from_iter([::std::rc::Rc::new(Child{parent: ::std::rc::Weak::clone(__w0),}),::std::rc::Rc::new(Child{parent: ::std::rc::Weak::clone(__w0),}),]),})"
+    );
+
+    let rebuilt = ::std::rc::Rc::new_cyclic(|__w0| Parent {
+        children: ::std::vec::Vec::from_iter([
+            ::std::rc::Rc::new(Child {
+                parent: ::std::rc::Weak::clone(__w0),
+            }),
+            ::std::rc::Rc::new(Child {
+                parent: ::std::rc::Weak::clone(__w0),
+            }),
+        ]),
+    });
+    for child in &rebuilt.children {
+        assert!(Rc::ptr_eq(&child.parent.upgrade().unwrap(), &rebuilt));
+    }
+
+    let mut unshared = Options::default();
+    unshared.share_pointers = false;
+    assert!(matches!(
+        buildstr::try_render_with(&parent, &unshared),
+        Err(RenderError::Cycle { .. })
+    ));
+}
+
+#[test]
+fn depth() {
+    #[derive(BuildStr)]
+    enum List {
+        Cons(u8, Box<List>),
+        Nil,
+    }
+
+    let list = (0..10).fold(List::Nil, |list, i| List::Cons(i, Box::new(list)));
+    let mut limited = Options::default();
+    limited.max_depth = Some(5);
+    let error = buildstr::try_render_with(&list, &limited).unwrap_err();
+    assert!(matches!(error, RenderError::DepthLimit { limit: 5, ref path } if path.len() == 6));
+    assert_eq!(error.to_string(), "maximum depth of 5 exceeded while rendering: cycle::depth::List -> cycle::depth::List -> cycle::depth::List -> cycle::depth::List -> cycle::depth::List -> cycle::depth::List");
+
+    let mut unlimited = Options::default();
+    unlimited.max_depth = None;
+    assert_eq!(
+        buildstr::render_with(&list, &unlimited),
+        list.to_build_string()
+    );
+
+    // There is no limit by default, so deep values don't make `to_build_string` panic
+    std::thread::Builder::new()
+        .stack_size(64 << 20)
+        .spawn(|| {
+            let list = (0..1000).fold(List::Nil, |list, i| List::Cons(i as u8, Box::new(list)));
+            assert!(list.to_build_string().starts_with("List::Cons(231u8,"));
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use buildstr::{BuildStr, Options};
+
+#[derive(BuildStr, Debug, Clone, PartialEq)]
+struct Point {
+    name: String,
+    x: i32,
+    y: i32,
+}
+
+fn options(min: usize) -> Options {
+    let mut options = Options::default();
+    options.dedup = Some(min);
+    options
+}
+
+fn points() -> Vec<Point> {
+    let point = Point {
+        name: String::from("origin"),
+        x: 0,
+        y: 0,
+    };
+    vec![point.clone(), point.clone(), Point { x: 1, ..point }]
+}
+
+#[test]
+fn dedup() {
+    let points = points();
+    assert_eq!(
+        buildstr::render_with(&points, &options(16)),
+        "{let __c0=::std::string::String::from(\"origin\");let __c1=Point{name: ::core::clone::Clone::clone(&__c0),x: 0i32,y: 0i32,};::std::vec::Vec::from_iter([::core::clone::Clone::clone(&__c1),::core::clone::Clone::clone(&__c1),Point{name: ::core::clone::Clone::clone(&__c0),x: 1i32,y: 0i32,},])}"
+    );
+
+    let rebuilt: Vec<Point> = {
+        let __c0 = ::std::string::String::from("origin");
+        let __c1 = Point {
+            name: ::core::clone::Clone::clone(&__c0),
+            x: 0i32,
+            y: 0i32,
+        };
+        ::std::vec::Vec::from_iter([
+            ::core::clone::Clone::clone(&__c1),
+            ::core::clone::Clone::clone(&__c1),
+            Point {
+                name: ::core::clone::Clone::clone(&__c0),
+                x: 1i32,
+                y: 0i32,
+            },
+        ])
+    };
+    assert_eq!(rebuilt, points);
+}
+
+#[test]
+fn threshold() {
+    let points = points();
+    assert_eq!(
+        buildstr::render_with(&points, &options(1000)),
+        points.to_build_string()
+    );
+    assert_eq!(
+        buildstr::render_with(&points, &Options::default()),
+        points.to_build_string()
+    );
+}
+
+#[test]
+fn references() {
+    let value = (&String::from("long enough"), &String::from("long enough"));
+    assert_eq!(
+        buildstr::render_with(&value, &options(1)),
+        value.to_build_string()
+    );
+}
+
+#[test]
+fn hashmap() {
+    let map = HashMap::from([(2u8, points()), (1u8, points())]);
+    assert_eq!(
+        buildstr::render_with(&map, &options(16)),
+        "{let __c0=::std::string::String::from(\"origin\");let __c1=Point{name: ::core::clone::Clone::clone(&__c0),x: 0i32,y: 0i32,};let __c2=Point{name: ::core::clone::Clone::clone(&__c0),x: 1i32,y: 0i32,};let __c3=::std::vec::Vec::from_iter([::core::clone::Clone::clone(&__c1),::core::clone::Clone::clone(&__c1),::core::clone::Clone::clone(&__c2),]);{let mut map=::std::collections::HashMap::with_capacity_and_hasher(2,::std::collections::hash_map::RandomState::new());map.extend([(1u8,::core::clone::Clone::clone(&__c3)),(2u8,::core::clone::Clone::clone(&__c3)),]);map}}"
+    );
+}
@@ -0,0 +1,128 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use buildstr::{BuildStr, DynBuildStr, Options};
+
+trait Shape: DynBuildStr {
+    fn area(&self) -> u32;
+}
+buildstr::impl_dyn_buildstr!(dyn Shape);
+buildstr::impl_dyn_buildstr!(dyn Shape + Send + Sync);
+
+#[derive(BuildStr)]
+struct Circle {
+    radius: u32,
+}
+impl Shape for Circle {
+    fn area(&self) -> u32 {
+        3 * self.radius * self.radius
+    }
+}
+
+#[derive(BuildStr)]
+struct Square(u32);
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+#[test]
+fn boxed() {
+    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Circle { radius: 1 }), Box::new(Square(2))];
+    assert_eq!(
+        shapes.to_build_string(),
+        "::std::vec::Vec::from_iter([Box::new(Circle{radius: 1u32,}) as Box<dyn Shape>,Box::new(Square(2u32,)) as Box<dyn Shape>,])"
+    );
+    let rebuilt = ::std::vec::Vec::from_iter([
+        Box::new(Circle { radius: 1u32 }) as Box<dyn Shape>,
+        Box::new(Square(2u32)) as Box<dyn Shape>,
+    ]);
+    assert_eq!(rebuilt.iter().map(|s| s.area()).sum::<u32>(), 7);
+
+    let shape: Box<dyn Shape + Send + Sync> = Box::new(Square(3));
+    assert_eq!(
+        shape.to_build_string(),
+        "Box::new(Square(3u32,)) as Box<dyn Shape + Send + Sync>"
+    );
+}
+
+#[test]
+fn shared() {
+    let shape: Rc<dyn Shape> = Rc::new(Square(2));
+    assert_eq!(
+        buildstr::render_with(&(shape.clone(), shape), &Options::default()),
+        "{let __n0=::std::rc::Rc::new(Square(2u32,)) as ::std::rc::Rc<dyn Shape>;(::std::rc::Rc::clone(&__n0), ::std::rc::Rc::clone(&__n0))}"
+    );
+    let rebuilt = {
+        let __n0 = ::std::rc::Rc::new(Square(2u32)) as ::std::rc::Rc<dyn Shape>;
+        (::std::rc::Rc::clone(&__n0), ::std::rc::Rc::clone(&__n0))
+    };
+    assert!(Rc::ptr_eq(&rebuilt.0, &rebuilt.1));
+
+    let shape: Arc<dyn Shape + Send + Sync> = Arc::new(Circle { radius: 2 });
+    assert_eq!(
+        shape.to_build_string(),
+        "::std::sync::Arc::new(Circle{radius: 2u32,}) as ::std::sync::Arc<dyn Shape + Send + Sync>"
+    );
+}
+
+#[test]
+fn reference() {
+    let circle = Circle { radius: 1 };
+    let shape: &dyn Shape = &circle;
+    assert_eq!(
+        (&shape).to_build_string(),
+        "&Circle{radius: 1u32,} as &dyn Shape"
+    );
+    let rebuilt = &Circle { radius: 1u32 } as &dyn Shape;
+    assert_eq!(rebuilt.area(), 3);
+}
+
+#[test]
+fn any() {
+    use std::any::Any;
+
+    use buildstr::{RenderError, Renderer};
+
+    let values: Vec<Box<dyn Any>> = vec![Box::new(1u8), Box::new(Square(2))];
+    let mut renderer = Renderer::default();
+    renderer.register_any::<u8>();
+    assert!(matches!(
+        renderer.try_render(&values),
+        Err(RenderError::Unregistered { path }) if path.last() == Some(&"dyn ::std::any::Any")
+    ));
+    renderer.register_any::<Square>();
+    assert_eq!(
+        renderer.render(&values),
+        "::std::vec::Vec::from_iter([Box::new(1u8) as Box<dyn ::std::any::Any>,Box::new(Square(2u32,)) as Box<dyn ::std::any::Any>,])"
+    );
+    let rebuilt = ::std::vec::Vec::from_iter([
+        Box::new(1u8) as Box<dyn ::std::any::Any>,
+        Box::new(Square(2u32)) as Box<dyn ::std::any::Any>,
+    ]);
+    assert_eq!(
+        rebuilt[1].downcast_ref::<Square>().map(Shape::area),
+        Some(4)
+    );
+}
+
+#[test]
+fn casts_behind_references() {
+    let shape: Box<dyn Shape> = Box::new(Square(2));
+    assert_eq!(
+        (&&shape).to_build_string(),
+        "&(Box::new(Square(2u32,)) as Box<dyn Shape>)"
+    );
+    let rebuilt = &(Box::new(Square(2u32)) as Box<dyn Shape>);
+    assert_eq!(rebuilt.area(), 4);
+
+    let circle = Circle { radius: 1 };
+    let shape: &dyn Shape = &circle;
+    assert_eq!(
+        (&&shape).to_build_string(),
+        "&(&Circle{radius: 1u32,} as &dyn Shape)"
+    );
+    let rebuilt = &(&Circle { radius: 1u32 } as &dyn Shape);
+    assert_eq!(rebuilt.area(), 3);
+}
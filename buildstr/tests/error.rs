@@ -4,12 +4,125 @@ use buildstr::BuildStr;
 
 #[test]
 fn error() {
+    // The trait object itself is unsized, so it's written as its concrete value, and only
+    // references and pointers to it are coerced back to `dyn Error`
     assert_eq!(
         (&"5a".parse::<u8>().unwrap_err() as &dyn Error).to_build_string(),
-        r#"<&::std::primitive::str as ::std::convert::Into<::std::boxed::Box<dyn ::std::error::Error>>>::into("invalid digit found in string").as_ref()"#
+        r#"<::std::primitive::u8 as ::std::str::FromStr>::from_str("a").unwrap_err()"#
     );
     assert_eq!(
-          (<&::std::primitive::str as ::std::convert::Into<::std::boxed::Box<dyn ::std::error::Error>>>::into("invalid digit found in string").as_ref()).to_build_string(),
-        r#"<&::std::primitive::str as ::std::convert::Into<::std::boxed::Box<dyn ::std::error::Error>>>::into("invalid digit found in string").as_ref()"#
-    )
-}
\ No newline at end of file
+        (&(&"5a".parse::<u8>().unwrap_err() as &dyn Error)).to_build_string(),
+        r#"&<::std::primitive::u8 as ::std::str::FromStr>::from_str("a").unwrap_err() as &dyn ::std::error::Error"#
+    );
+    assert_eq!(
+        (&<::std::primitive::u8 as ::std::str::FromStr>::from_str("a").unwrap_err() as &dyn ::std::error::Error).to_string(),
+        "invalid digit found in string"
+    );
+    assert_eq!(
+          (&<&::std::primitive::str as ::std::convert::Into<::std::boxed::Box<dyn ::std::error::Error>>>::into("invalid digit found in string").as_ref()).to_build_string(),
+        r#"&::buildstr::ErrorMessage::new("invalid digit found in string") as &dyn ::std::error::Error"#
+    );
+    assert_eq!(
+        (&::buildstr::ErrorMessage::new("invalid digit found in string") as &dyn ::std::error::Error).to_string(),
+        "invalid digit found in string"
+    );
+}
+
+#[test]
+fn parse_int() {
+    let errors = [
+        "".parse::<u32>().unwrap_err(),
+        "1x".parse::<u32>().unwrap_err(),
+        "99999999999".parse::<u32>().unwrap_err(),
+        "-99999999999".parse::<i32>().unwrap_err(),
+        "0".parse::<std::num::NonZeroU32>().unwrap_err(),
+    ];
+    let rendered = errors.each_ref().map(|e| e.to_build_string());
+    assert_eq!(
+        rendered,
+        [
+            r#"<::std::primitive::u8 as ::std::str::FromStr>::from_str("").unwrap_err()"#,
+            r#"<::std::primitive::u8 as ::std::str::FromStr>::from_str("a").unwrap_err()"#,
+            r#"<::std::primitive::u8 as ::std::str::FromStr>::from_str("256").unwrap_err()"#,
+            r#"<::std::primitive::i8 as ::std::str::FromStr>::from_str("-129").unwrap_err()"#,
+            r#"<::core::num::NonZeroU8 as ::std::str::FromStr>::from_str("0").unwrap_err()"#,
+        ]
+    );
+    let rebuilt = [
+        <::std::primitive::u8 as ::std::str::FromStr>::from_str("").unwrap_err(),
+        <::std::primitive::u8 as ::std::str::FromStr>::from_str("a").unwrap_err(),
+        <::std::primitive::u8 as ::std::str::FromStr>::from_str("256").unwrap_err(),
+        <::std::primitive::i8 as ::std::str::FromStr>::from_str("-129").unwrap_err(),
+        <::core::num::NonZeroU8 as ::std::str::FromStr>::from_str("0").unwrap_err(),
+    ];
+    assert_eq!(rebuilt, errors);
+}
+
+#[test]
+fn parse() {
+    let float = "1.2.3".parse::<f32>().unwrap_err();
+    assert_eq!(float.to_build_string(), r#"<::std::primitive::f64 as ::std::str::FromStr>::from_str("a").unwrap_err()"#);
+    assert_eq!(<::std::primitive::f64 as ::std::str::FromStr>::from_str("a").unwrap_err(), float);
+    let empty = "".parse::<f64>().unwrap_err();
+    assert_eq!(empty.to_build_string(), r#"<::std::primitive::f64 as ::std::str::FromStr>::from_str("").unwrap_err()"#);
+    assert_eq!(<::std::primitive::f64 as ::std::str::FromStr>::from_str("").unwrap_err(), empty);
+
+    let boolean = "yes".parse::<bool>().unwrap_err();
+    assert_eq!(boolean.to_build_string(), r#"<::std::primitive::bool as ::std::str::FromStr>::from_str("").unwrap_err()"#);
+    assert_eq!(<::std::primitive::bool as ::std::str::FromStr>::from_str("").unwrap_err(), boolean);
+
+    let char = "xy".parse::<char>().unwrap_err();
+    assert_eq!(char.to_build_string(), r#"<::std::primitive::char as ::std::str::FromStr>::from_str("aa").unwrap_err()"#);
+    assert_eq!(<::std::primitive::char as ::std::str::FromStr>::from_str("aa").unwrap_err(), char);
+
+    let addr = "localhost:80".parse::<std::net::SocketAddrV4>().unwrap_err();
+    assert_eq!(addr.to_build_string(), r#"<::std::net::SocketAddrV4 as ::std::str::FromStr>::from_str("").unwrap_err()"#);
+    assert_eq!(<::std::net::SocketAddrV4 as ::std::str::FromStr>::from_str("").unwrap_err(), addr);
+    let addr = "::1".parse::<std::net::Ipv4Addr>().unwrap_err();
+    assert_eq!(<::std::net::Ipv4Addr as ::std::str::FromStr>::from_str("").unwrap_err(), addr);
+    assert_eq!(addr.to_build_string(), r#"<::std::net::Ipv4Addr as ::std::str::FromStr>::from_str("").unwrap_err()"#);
+}
+
+#[test]
+fn convert() {
+    let int = u8::try_from(1000u32).unwrap_err();
+    assert_eq!(int.to_build_string(), "<::std::primitive::u8 as ::core::convert::TryFrom<::std::primitive::i8>>::try_from(-1i8).unwrap_err()");
+    assert_eq!(<::std::primitive::u8 as ::core::convert::TryFrom<::std::primitive::i8>>::try_from(-1i8).unwrap_err(), int);
+
+    let slice = <[u8; 2]>::try_from(&[1u8][..]).unwrap_err();
+    assert_eq!(slice.to_build_string(), "::core::convert::TryInto::<[();1]>::try_into(&[]as&[()]).unwrap_err()");
+    assert_eq!(::core::convert::TryInto::<[();1]>::try_into(&[]as&[()]).unwrap_err().to_string(), slice.to_string());
+}
+
+#[test]
+fn utf8() {
+    let cases: [&[u8]; 5] = [b"ab\xff", b"abc\xe2\x82", b"\xe2\x82(", b"a\xf0\x90\x80(", b"\xc0"];
+    let rendered = cases.map(|bytes| std::str::from_utf8(bytes).unwrap_err().to_build_string());
+    assert_eq!(
+        rendered,
+        [
+            r#"::std::string::String::from_utf8(::std::vec::Vec::from(b"aa\xff")).unwrap_err().utf8_error()"#,
+            r#"::std::string::String::from_utf8(::std::vec::Vec::from(b"aaa\xe2")).unwrap_err().utf8_error()"#,
+            r#"::std::string::String::from_utf8(::std::vec::Vec::from(b"\xe2\x82(")).unwrap_err().utf8_error()"#,
+            r#"::std::string::String::from_utf8(::std::vec::Vec::from(b"a\xf0\x90\x80(")).unwrap_err().utf8_error()"#,
+            r#"::std::string::String::from_utf8(::std::vec::Vec::from(b"\xff")).unwrap_err().utf8_error()"#,
+        ]
+    );
+    let rebuilt = [
+        ::std::string::String::from_utf8(::std::vec::Vec::from(b"aa\xff")).unwrap_err().utf8_error(),
+        ::std::string::String::from_utf8(::std::vec::Vec::from(b"aaa\xe2")).unwrap_err().utf8_error(),
+        ::std::string::String::from_utf8(::std::vec::Vec::from(b"\xe2\x82(")).unwrap_err().utf8_error(),
+        ::std::string::String::from_utf8(::std::vec::Vec::from(b"a\xf0\x90\x80(")).unwrap_err().utf8_error(),
+        ::std::string::String::from_utf8(::std::vec::Vec::from(b"\xff")).unwrap_err().utf8_error(),
+    ];
+    assert_eq!(rebuilt, cases.map(|bytes| std::str::from_utf8(bytes).unwrap_err()));
+
+    let long = [vec![b'z'; 100], vec![0xff]].concat();
+    let error = std::str::from_utf8(&long).unwrap_err();
+    assert_eq!(error.to_build_string(), r#"::std::string::String::from_utf8([&[b'a'; 100][..], b"\xff"].concat()).unwrap_err().utf8_error()"#);
+    assert_eq!(::std::string::String::from_utf8([&[b'a'; 100][..], b"\xff"].concat()).unwrap_err().utf8_error(), error);
+
+    let error = String::from_utf8(b"a\xff".to_vec()).unwrap_err();
+    assert_eq!(error.to_build_string(), "::std::string::String::from_utf8(::std::vec::Vec::from_iter([97u8,255u8,])).unwrap_err()");
+    assert_eq!(::std::string::String::from_utf8(::std::vec::Vec::from_iter([97u8,255u8,])).unwrap_err(), error);
+}
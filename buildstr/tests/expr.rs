@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+
+use buildstr::{BuildStr, Expr, MethodCall, Options, Path, Style};
+
+#[derive(BuildStr)]
+struct Point {
+    name: String,
+    x: i32,
+    y: f64,
+}
+
+fn point() -> Point {
+    Point {
+        name: "origin".into(),
+        x: 0,
+        y: -2.5,
+    }
+}
+
+#[test]
+fn tree() {
+    let expr = point().to_build_expr();
+    let Expr::StructLit { path, fields, .. } = &expr else {
+        panic!("expected a struct literal, found {expr:?}")
+    };
+    assert_eq!(*path, Path::new(false, ["Point"]));
+    assert_eq!(fields[1], ("x".into(), Expr::Lit("0i32".into())));
+    assert_eq!(
+        fields[2].1,
+        Expr::Unary {
+            op: '-',
+            expr: Box::new(Expr::Lit("2.5f64".into())),
+        }
+    );
+
+    let expr = "a".parse::<u8>().unwrap_err().to_build_expr();
+    let Expr::MethodChain { receiver, calls } = expr else {
+        panic!("expected a method chain")
+    };
+    assert!(matches!(*receiver, Expr::Call { .. }));
+    assert_eq!(
+        calls,
+        [MethodCall {
+            name: "unwrap_err".into(),
+            generics: None,
+            args: Vec::new(),
+        }]
+    );
+}
+
+#[test]
+fn display() {
+    let values = [
+        vec![point()].to_build_expr(),
+        BTreeMap::from([("k", Some(1u8))]).to_build_expr(),
+        (1u8..=4, ..4u8).to_build_expr(),
+        std::alloc::Layout::new::<u64>().to_build_expr(),
+        <[u8; 1]>::try_from(&[][..]).unwrap_err().to_build_expr(),
+        std::borrow::Cow::Borrowed("x").to_build_expr(),
+        std::marker::PhantomData::<BTreeMap<u8, u8>>.to_build_expr(),
+        (-0.0f64, -5i8, 'a', "text", true).to_build_expr(),
+    ];
+    for expr in &values {
+        assert!(!matches!(expr, Expr::Verbatim(_)), "{expr}");
+    }
+    assert_eq!(
+        values.each_ref().map(ToString::to_string),
+        [
+            vec![point()].to_build_string(),
+            BTreeMap::from([("k", Some(1u8))]).to_build_string(),
+            (1u8..=4, ..4u8).to_build_string(),
+            std::alloc::Layout::new::<u64>().to_build_string(),
+            <[u8; 1]>::try_from(&[][..]).unwrap_err().to_build_string(),
+            std::borrow::Cow::Borrowed("x").to_build_string(),
+            std::marker::PhantomData::<BTreeMap<u8, u8>>.to_build_string(),
+            (-0.0f64, -5i8, 'a', "text", true).to_build_string(),
+        ]
+    );
+}
+
+#[test]
+fn pretty() {
+    assert_eq!(
+        point().to_build_expr().to_pretty_string(),
+        "Point { name: ::std::string::String::from(\"origin\"), x: 0i32, y: -2.5f64 }"
+    );
+    assert_eq!(
+        vec![point(), point()].to_build_expr().to_pretty_string(),
+        r#"::std::vec::Vec::from_iter([
+    Point { name: ::std::string::String::from("origin"), x: 0i32, y: -2.5f64 },
+    Point { name: ::std::string::String::from("origin"), x: 0i32, y: -2.5f64 },
+])"#
+    );
+
+    let mut options = Options::default();
+    options.style = Style::Idiomatic;
+    assert_eq!(
+        buildstr::render_with(&std::collections::HashMap::from([(1u8, 'a')]), &options),
+        r"{
+    let mut map = ::std::collections::HashMap::with_capacity_and_hasher(
+        1,
+        ::std::collections::hash_map::RandomState::new(),
+    );
+    map.extend([(1u8, 'a')]);
+    map
+}"
+    );
+}
@@ -1,6 +1,6 @@
-use buildstr::BuildStr;
+#![allow(clippy::manual_c_str_literals)]
 
-type Result = std::result::Result<(), Box<dyn std::error::Error>>;
+use buildstr::BuildStr;
 
 #[test]
 fn cstr() {
@@ -12,6 +12,15 @@ fn cstr() {
         ::std::ffi::CStr::from_bytes_with_nul(&[104u8,101u8,108u8,108u8,111u8,0u8,]).unwrap().to_build_string(),
         "::std::ffi::CStr::from_bytes_with_nul(&[104u8,101u8,108u8,108u8,111u8,0u8,]).unwrap()"
     );
+    let cstr: &std::ffi::CStr = c"hi";
+    assert_eq!(
+        (&cstr).to_build_string(),
+        "::std::ffi::CStr::from_bytes_with_nul(&[104u8,105u8,0u8,]).unwrap()"
+    );
+    assert_eq!(
+        (&&cstr).to_build_string(),
+        "&::std::ffi::CStr::from_bytes_with_nul(&[104u8,105u8,0u8,]).unwrap()"
+    );
 }
 
 #[test]
@@ -42,6 +51,24 @@ fn osstring() {
     );
 }
 
+#[cfg(unix)]
+#[test]
+fn non_utf8() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let s = ::std::ffi::OsStr::from_bytes(b"f\xffoo");
+    assert_eq!(
+        s.to_build_string(),
+        "<::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(b\"f\\xffoo\")"
+    );
+    assert_eq!(<::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(b"f\xffoo"), s);
+    assert_eq!(
+        s.to_os_string().to_build_string(),
+        "::std::ffi::OsString::from(<::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(b\"f\\xffoo\"))"
+    );
+    assert_eq!(::std::ffi::OsString::from(<::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(b"f\xffoo")), s);
+}
+
 #[test]
 fn from_bytes_with_nul_error() {
     assert_eq!(
@@ -0,0 +1,76 @@
+use std::io::{Cursor, Error, ErrorKind, Read, Seek, SeekFrom};
+
+use buildstr::BuildStr;
+
+#[test]
+fn error_kind() {
+    assert_eq!(
+        ErrorKind::NotFound.to_build_string(),
+        "::std::io::ErrorKind::NotFound"
+    );
+    assert_eq!(
+        ErrorKind::UnexpectedEof.to_build_string(),
+        "::std::io::ErrorKind::UnexpectedEof"
+    );
+}
+
+#[test]
+fn error() {
+    let error = Error::new(ErrorKind::InvalidData, "bad header");
+    assert_eq!(
+        error.to_build_string(),
+        "::std::io::Error::new(::std::io::ErrorKind::InvalidData, \"bad header\")"
+    );
+    let rebuilt = ::std::io::Error::new(::std::io::ErrorKind::InvalidData, "bad header");
+    assert_eq!(rebuilt.kind(), error.kind());
+    assert_eq!(rebuilt.to_string(), error.to_string());
+
+    assert_eq!(
+        Error::from(ErrorKind::TimedOut).to_build_string(),
+        "::std::io::Error::from(::std::io::ErrorKind::TimedOut)"
+    );
+    assert_eq!(
+        Error::from_raw_os_error(2).to_build_string(),
+        "::std::io::Error::from_raw_os_error(2)"
+    );
+
+    let error = Error::new(ErrorKind::InvalidData, "x".parse::<u8>().unwrap_err());
+    assert_eq!(
+        error.to_build_string(),
+        "::std::io::Error::new(::std::io::ErrorKind::InvalidData, <::std::primitive::u8 as ::std::str::FromStr>::from_str(\"a\").unwrap_err())"
+    );
+}
+
+#[test]
+fn cursor() {
+    let mut cursor = Cursor::new(vec![1u8, 2, 3]);
+    assert_eq!(
+        cursor.to_build_string(),
+        "::std::io::Cursor::new(::std::vec::Vec::from_iter([1u8,2u8,3u8,]))"
+    );
+    cursor.seek(SeekFrom::Start(2)).unwrap();
+    assert_eq!(
+        cursor.to_build_string(),
+        "{let mut cursor=::std::io::Cursor::new(::std::vec::Vec::from_iter([1u8,2u8,3u8,]));cursor.set_position(2u64);cursor}"
+    );
+    let mut rebuilt = {
+        let mut cursor = ::std::io::Cursor::new(::std::vec::Vec::from_iter([1u8, 2u8, 3u8]));
+        cursor.set_position(2u64);
+        cursor
+    };
+    let mut rest = Vec::new();
+    rebuilt.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, [3]);
+
+    assert_eq!(
+        SeekFrom::End(-4).to_build_string(),
+        "::std::io::SeekFrom::End(-4i64)"
+    );
+}
+
+#[test]
+fn unit() {
+    assert_eq!(std::io::empty().to_build_string(), "::std::io::empty()");
+    assert_eq!(std::io::sink().to_build_string(), "::std::io::sink()");
+    assert_eq!(std::io::stdout().to_build_string(), "::std::io::stdout()");
+}
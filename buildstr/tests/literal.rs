@@ -0,0 +1,170 @@
+use buildstr::{BuildStr, IntFormat, Options, Suffixes};
+
+#[derive(BuildStr, Debug, Clone, PartialEq)]
+struct Sample<T> {
+    id: u32,
+    values: Vec<(u8, f64)>,
+    extra: T,
+}
+
+fn options(suffixes: Suffixes) -> Options {
+    let mut options = Options::default();
+    options.suffixes = suffixes;
+    options
+}
+
+#[test]
+fn inferred() {
+    let sample = Sample {
+        id: 7,
+        values: vec![(1, 0.5), (2, 1000.0)],
+        extra: 3i64,
+    };
+    assert_eq!(
+        buildstr::render_with(&sample, &options(Suffixes::Inferred)),
+        "Sample{id: 7,values: ::std::vec::Vec::from_iter([(1, 0.5),(2, 1000.0),]),extra: 3i64,}"
+    );
+    let rebuilt = Sample {
+        id: 7,
+        values: ::std::vec::Vec::from_iter([(1, 0.5), (2, 1000.0)]),
+        extra: 3i64,
+    };
+    assert_eq!(rebuilt, sample);
+
+    // The first element fixes the type of the others
+    let values = [(1u8, 0.5f64), (2, 1.5)];
+    assert_eq!(
+        buildstr::render_with(&values, &options(Suffixes::Inferred)),
+        "[(1u8, 0.5f64),(2, 1.5),]"
+    );
+    assert_eq!([(1u8, 0.5f64), (2, 1.5)], values);
+
+    // The value is in a position with a known type
+    assert_eq!(
+        buildstr::render_with(&values, &options(Suffixes::InferredTyped)),
+        "[(1, 0.5),(2, 1.5),]"
+    );
+    static VALUES: [(u8, f64); 2] = [(1, 0.5), (2, 1.5)];
+    assert_eq!(VALUES, values);
+}
+
+#[test]
+fn unfixed() {
+    // The suffixes are kept until a literal at the same position fixed their type
+    let values = vec![None, Some(1u8)];
+    assert_eq!(
+        buildstr::render_with(&values, &options(Suffixes::Inferred)),
+        "::std::vec::Vec::from_iter([::core::option::Option::None,::core::option::Option::Some(1u8),])"
+    );
+    let rebuilt = ::std::vec::Vec::from_iter([
+        ::core::option::Option::None,
+        ::core::option::Option::Some(1u8),
+    ]);
+    assert_eq!(rebuilt, values);
+
+    let values = vec![(1u8, None), (2, Some(3u8)), (4, Some(5))];
+    assert_eq!(
+        buildstr::render_with(&values, &options(Suffixes::Inferred)),
+        "::std::vec::Vec::from_iter([(1u8, ::core::option::Option::None),(2, ::core::option::Option::Some(3u8)),(4, ::core::option::Option::Some(5)),])"
+    );
+    let rebuilt = ::std::vec::Vec::from_iter([
+        (1u8, ::core::option::Option::None),
+        (2, ::core::option::Option::Some(3u8)),
+        (4, ::core::option::Option::Some(5)),
+    ]);
+    assert_eq!(rebuilt, values);
+
+    let values: [Result<u8, u16>; 2] = [Ok(1), Err(2)];
+    assert_eq!(
+        buildstr::render_with(&values, &options(Suffixes::Inferred)),
+        "[::core::result::Result::Ok(1u8),::core::result::Result::Err(2u16),]"
+    );
+    let rebuilt = [
+        ::core::result::Result::Ok(1u8),
+        ::core::result::Result::Err(2u16),
+    ];
+    assert_eq!(rebuilt, values);
+}
+
+#[test]
+fn dedup() {
+    // The same expression builds different types, so it can't be shared
+    let sample = Sample {
+        id: 1,
+        values: Vec::new(),
+        extra: Vec::<u16>::new(),
+    };
+    let mut options = options(Suffixes::Inferred);
+    options.dedup = Some(20);
+    assert_eq!(
+        buildstr::render_with(&[sample.clone(), sample], &options),
+        "{let __c0=::std::vec::Vec::from_iter([]);let __c1=::std::vec::Vec::from_iter([]);let __c2=Sample{id: 1,values: ::core::clone::Clone::clone(&__c0),extra: ::core::clone::Clone::clone(&__c1),};[::core::clone::Clone::clone(&__c2),::core::clone::Clone::clone(&__c2),]}"
+    );
+    let rebuilt: [Sample<Vec<u16>>; 2] = {
+        let __c0 = ::std::vec::Vec::from_iter([]);
+        let __c1 = ::std::vec::Vec::from_iter([]);
+        let __c2 = Sample {
+            id: 1,
+            values: ::core::clone::Clone::clone(&__c0),
+            extra: ::core::clone::Clone::clone(&__c1),
+        };
+        [
+            ::core::clone::Clone::clone(&__c2),
+            ::core::clone::Clone::clone(&__c2),
+        ]
+    };
+    assert_eq!(rebuilt[0].id, 1);
+}
+
+#[test]
+fn int_format() {
+    let options = |int_format, digit_separators| {
+        let mut options = Options::default();
+        options.int_format = int_format;
+        options.digit_separators = digit_separators;
+        options
+    };
+    let render = |value: &(u32, i16, u8), int_format, separators| {
+        buildstr::render_with(value, &options(int_format, separators))
+    };
+    let value = (1_000_000u32, -300i16, 5u8);
+    assert_eq!(
+        render(&value, IntFormat::Decimal, true),
+        "(1_000_000_u32, -300_i16, 5_u8)"
+    );
+    assert_eq!(
+        render(&value, IntFormat::Hex, false),
+        "(0xf4240_u32, -0x12c_i16, 0x5_u8)"
+    );
+    assert_eq!(
+        render(&value, IntFormat::Hex, true),
+        "(0xf_4240_u32, -0x12c_i16, 0x5_u8)"
+    );
+    assert_eq!(
+        render(&value, IntFormat::Binary, true),
+        "(0b1111_0100_0010_0100_0000_u32, -0b1_0010_1100_i16, 0b101_u8)"
+    );
+    assert_eq!(
+        (0b1111_0100_0010_0100_0000_u32, -0b1_0010_1100_i16, 0b101_u8),
+        value
+    );
+
+    assert_eq!(
+        buildstr::render_with(&i32::MIN, &options(IntFormat::Hex, false)),
+        "-0x80000000_i32"
+    );
+    assert_eq!(-0x80000000_i32, i32::MIN);
+}
+
+#[test]
+fn floats() {
+    let values = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e-7];
+    assert_eq!(
+        values.to_build_string(),
+        "[::core::primitive::f64::NAN,::core::primitive::f64::INFINITY,::core::primitive::f64::NEG_INFINITY,0.0000001f64,]"
+    );
+    assert_eq!(
+        buildstr::render_with(&&values[3..], &options(Suffixes::InferredTyped)),
+        "&[1e-7,]"
+    );
+}
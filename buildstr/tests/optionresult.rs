@@ -2,6 +2,7 @@ use buildstr::BuildStr;
 
 #[test]
 fn option() {
+    #[allow(unused)]
     #[derive(BuildStr)]
     struct Options {
         background: Option<(u8, u8, u8)>,
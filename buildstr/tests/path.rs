@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use buildstr::BuildStr;
+
+#[test]
+fn path() {
+    assert_eq!(
+        Path::new("src/lib.rs").to_build_string(),
+        "::std::path::Path::new(\"src/lib.rs\")"
+    );
+    assert_eq!(
+        PathBuf::from("src/lib.rs").to_build_string(),
+        "::std::path::PathBuf::from(\"src/lib.rs\")"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = Path::new(OsStr::from_bytes(b"out/f\xffoo.rs"));
+    assert_eq!(
+        path.to_build_string(),
+        "::std::path::Path::new(<::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(b\"out/f\\xffoo.rs\"))"
+    );
+    assert_eq!(
+        ::std::path::Path::new(
+            <::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(b"out/f\xffoo.rs")
+        ),
+        path
+    );
+
+    let files = vec![PathBuf::from("a.rs"), path.to_path_buf()];
+    assert_eq!(
+        files.to_build_string(),
+        "::std::vec::Vec::from_iter([::std::path::PathBuf::from(\"a.rs\"),::std::path::PathBuf::from(<::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(b\"out/f\\xffoo.rs\")),])"
+    );
+    assert_eq!(
+        vec![
+            ::std::path::PathBuf::from("a.rs"),
+            ::std::path::PathBuf::from(
+                <::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(
+                    b"out/f\xffoo.rs"
+                )
+            )
+        ],
+        files
+    );
+}
+
+#[test]
+fn component() {
+    let components = Path::new("/src/./../lib.rs")
+        .components()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        components.to_build_string(),
+        "::std::vec::Vec::from_iter([::std::path::Component::RootDir,::std::path::Component::Normal(::std::ffi::OsStr::new(\"src\")),::std::path::Component::ParentDir,::std::path::Component::Normal(::std::ffi::OsStr::new(\"lib.rs\")),])"
+    );
+    assert_eq!(
+        Path::new("./a")
+            .components()
+            .next()
+            .unwrap()
+            .to_build_string(),
+        "::std::path::Component::CurDir"
+    );
+    assert_eq!(
+        vec![
+            ::std::path::Component::RootDir,
+            ::std::path::Component::Normal(::std::ffi::OsStr::new("src")),
+            ::std::path::Component::ParentDir,
+            ::std::path::Component::Normal(::std::ffi::OsStr::new("lib.rs"))
+        ],
+        components
+    );
+}
+
+#[test]
+fn display() {
+    assert_eq!(
+        Path::new("src/lib.rs").display().to_build_string(),
+        "::std::path::Path::new(\"src/lib.rs\").display()"
+    );
+}
+
+#[test]
+fn ancestors() {
+    let mut ancestors = Path::new("a/b").ancestors();
+    ancestors.next();
+    assert_eq!(
+        ancestors.to_build_string(),
+        "::std::path::Path::new(\"a\").ancestors()"
+    );
+    ancestors.by_ref().for_each(drop);
+    assert_eq!(
+        ancestors.to_build_string(),
+        "{let mut ancestors=::std::path::Path::new(\"\").ancestors();ancestors.next();ancestors}"
+    );
+    let mut rebuilt = {
+        let mut ancestors = ::std::path::Path::new("").ancestors();
+        ancestors.next();
+        ancestors
+    };
+    assert_eq!(rebuilt.next(), None);
+}
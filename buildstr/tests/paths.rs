@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use buildstr::{BuildStr, Options, Renderer};
+
+mod model {
+    use buildstr::BuildStr;
+
+    #[derive(BuildStr, Debug, PartialEq)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    #[derive(BuildStr, Debug, PartialEq)]
+    pub enum Shape {
+        Dot(Point),
+        Empty,
+    }
+}
+
+use model::{Point, Shape};
+
+fn options() -> Options {
+    let mut options = Options::default();
+    options.qualify_types = true;
+    options
+}
+
+#[test]
+fn qualify() {
+    let shape = Shape::Dot(Point { x: 1, y: 2 });
+    assert_eq!(
+        shape.to_build_string(),
+        "Shape::Dot(Point{x: 1i32,y: 2i32,},)"
+    );
+    assert_eq!(
+        buildstr::render_with(&shape, &options()),
+        "crate::model::Shape::Dot(crate::model::Point{x: 1i32,y: 2i32,},)"
+    );
+    assert_eq!(
+        buildstr::render_with(&Shape::Empty, &options()),
+        "crate::model::Shape::Empty"
+    );
+
+    let rebuilt = crate::model::Shape::Dot(crate::model::Point { x: 1i32, y: 2i32 });
+    assert_eq!(rebuilt, shape);
+
+    // A type defined inside a function can't be named from its module
+    #[derive(BuildStr)]
+    struct Local(u8);
+    assert_eq!(buildstr::render_with(&Local(1), &options()), "Local(1u8,)");
+}
+
+#[test]
+fn rewrite() {
+    let mut options = options();
+    options.rewrite_paths = vec![
+        ("crate::model".into(), "::model_types".into()),
+        ("::std::collections".into(), "::alloc::collections".into()),
+    ];
+    let map = BTreeMap::from([(1u8, Point { x: 0, y: 0 })]);
+    assert_eq!(
+        buildstr::render_with(&map, &options),
+        "::alloc::collections::BTreeMap::from_iter([(1u8,::model_types::Point{x: 0i32,y: 0i32,}),])"
+    );
+
+    // Only whole segments are replaced
+    let mut options = self::options();
+    options.rewrite_paths = vec![("crate::mod".into(), "::other".into())];
+    assert_eq!(
+        buildstr::render_with(&Shape::Empty, &options),
+        "crate::model::Shape::Empty"
+    );
+}
+
+#[test]
+fn hoist() {
+    let mut options = options();
+    options.hoist_uses = true;
+    let shapes = vec![Shape::Dot(Point { x: 1, y: 2 }), Shape::Empty];
+    let rendered = buildstr::render_with(&shapes, &options);
+    assert_eq!(
+        rendered,
+        "{use ::std::vec::Vec;use crate::model::Point;use crate::model::Shape;Vec::from_iter([Shape::Dot(Point{x: 1i32,y: 2i32,},),Shape::Empty,])}"
+    );
+
+    let rebuilt = {
+        use crate::model::Point;
+        use crate::model::Shape;
+        use std::vec::Vec;
+        Vec::from_iter([Shape::Dot(Point { x: 1i32, y: 2i32 }), Shape::Empty])
+    };
+    assert_eq!(rebuilt, shapes);
+}
+
+#[test]
+fn conflicts() {
+    let mut options = Options::default();
+    options.hoist_uses = true;
+    // `Point` is used without a path, so it can't be imported
+    let value = (Point { x: 0, y: 0 }, String::new());
+    assert_eq!(
+        buildstr::render_with(&value, &options),
+        "{use ::std::string::String;(Point{x: 0i32,y: 0i32,}, String::from(\"\"))}"
+    );
+
+    // Values with shared nodes are hoisted into their block
+    let shared = std::rc::Rc::new(1u8);
+    assert_eq!(
+        buildstr::render_with(&(shared.clone(), shared), &options),
+        "{use ::std::rc::Rc;let __n0=Rc::new(1u8);(Rc::clone(&__n0), Rc::clone(&__n0))}"
+    );
+}
+
+#[test]
+fn associated() {
+    let mut options = Options::default();
+    options.hoist_uses = true;
+    // Associated items and primitive types can't be imported
+    let error = char::try_from(u32::MAX).unwrap_err();
+    assert_eq!(
+        buildstr::render_with(&error, &options),
+        "::std::primitive::char::try_from(::std::primitive::u32::MAX).unwrap_err()"
+    );
+}
+
+#[test]
+fn overrides() {
+    let mut renderer = Renderer::default();
+    renderer.options.rewrite_paths = vec![("::std::string".into(), "::alloc::string".into())];
+    renderer.register(|s: &String| format!("::std::string::String::from({s:?})"));
+    // The output of overrides is rewritten too
+    assert_eq!(
+        renderer.render(&Some(String::from("a"))),
+        "::core::option::Option::Some(::alloc::string::String::from(\"a\"))"
+    );
+}
@@ -0,0 +1,149 @@
+use std::process::{Command, ExitCode, ExitStatus, Output, Stdio};
+
+use buildstr::{BuildStr, RenderError};
+
+#[test]
+fn command() {
+    let mut command = Command::new("cargo");
+    command
+        .args(["build", "--release"])
+        .env("A", "1")
+        .env_remove("B")
+        .env("C", "2")
+        .env_remove("D")
+        .current_dir("target");
+    assert_eq!(
+        command.to_build_string(),
+        "{let mut command=::std::process::Command::new(\"cargo\");command.arg(\"build\");command.arg(\"--release\");command.env(\"A\",\"1\");command.env_remove(\"B\");command.env(\"C\",\"2\");command.env_remove(\"D\");command.current_dir(\"target\");command}"
+    );
+    let rebuilt = {
+        let mut command = ::std::process::Command::new("cargo");
+        command.arg("build");
+        command.arg("--release");
+        command.env("A", "1");
+        command.env_remove("B");
+        command.env("C", "2");
+        command.env_remove("D");
+        command.current_dir("target");
+        command
+    };
+    assert_eq!(format!("{rebuilt:#?}"), format!("{command:#?}"));
+}
+
+#[cfg(unix)]
+#[test]
+fn mixed_args() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut command = Command::new("cat");
+    command.arg("-n").arg(OsStr::from_bytes(b"a\xff"));
+    assert_eq!(
+        command.to_build_string(),
+        "{let mut command=::std::process::Command::new(\"cat\");command.arg(\"-n\");command.arg(<::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(b\"a\\xff\"));command}"
+    );
+    let rebuilt = {
+        let mut command = ::std::process::Command::new("cat");
+        command.arg("-n");
+        command.arg(<::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(b"a\xff"));
+        command
+    };
+    assert_eq!(format!("{rebuilt:#?}"), format!("{command:#?}"));
+}
+
+#[test]
+fn env_clear() {
+    let mut command = Command::new("ls");
+    command.env_clear().env("PATH", "/bin");
+    assert_eq!(
+        command.to_build_string(),
+        "{let mut command=::std::process::Command::new(\"ls\");command.env_clear();command.env(\"PATH\",\"/bin\");command}"
+    );
+    let mut command = Command::new("ls");
+    command.env_clear();
+    assert_eq!(
+        command.to_build_string(),
+        "{let mut command=::std::process::Command::new(\"ls\");command.env_clear();command}"
+    );
+}
+
+#[test]
+fn stdio() {
+    let mut command = Command::new("ls");
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit());
+    assert_eq!(
+        command.to_build_string(),
+        "{let mut command=::std::process::Command::new(\"ls\");command.stdin(::std::process::Stdio::null());command.stdout(::std::process::Stdio::piped());command.stderr(::std::process::Stdio::inherit());command}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn redirected() {
+    let mut command = Command::new("ls");
+    command.stdout(std::io::stderr());
+    assert!(matches!(
+        buildstr::try_render_with(&command, &buildstr::Options::default()),
+        Err(RenderError::Redirected { path }) if path.last() == Some(&"std::process::Command")
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn exit_status() {
+    use std::os::unix::process::ExitStatusExt;
+
+    let status = ExitStatus::from_raw(256);
+    assert_eq!(
+        status.to_build_string(),
+        "<::std::process::ExitStatus as ::std::os::unix::process::ExitStatusExt>::from_raw(256i32)"
+    );
+    assert_eq!(
+        <::std::process::ExitStatus as ::std::os::unix::process::ExitStatusExt>::from_raw(256i32)
+            .code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn exit_code() {
+    assert_eq!(
+        ExitCode::SUCCESS.to_build_string(),
+        "::std::process::ExitCode::SUCCESS"
+    );
+    assert_eq!(
+        ExitCode::FAILURE.to_build_string(),
+        "::std::process::ExitCode::FAILURE"
+    );
+    assert_eq!(
+        ExitCode::from(42).to_build_string(),
+        "::std::process::ExitCode::from(42u8)"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn output() {
+    use std::os::unix::process::ExitStatusExt;
+
+    let output = Output {
+        status: ExitStatus::from_raw(0),
+        stdout: b"ok\n".to_vec(),
+        stderr: Vec::new(),
+    };
+    assert_eq!(
+        output.to_build_string(),
+        "::std::process::Output{status: <::std::process::ExitStatus as ::std::os::unix::process::ExitStatusExt>::from_raw(0i32),stdout: ::std::vec::Vec::from_iter([111u8,107u8,10u8,]),stderr: ::std::vec::Vec::from_iter([]),}"
+    );
+    let rebuilt = ::std::process::Output {
+        status: <::std::process::ExitStatus as ::std::os::unix::process::ExitStatusExt>::from_raw(
+            0i32,
+        ),
+        stdout: ::std::vec::Vec::from_iter([111u8, 107u8, 10u8]),
+        stderr: ::std::vec::Vec::from_iter([]),
+    };
+    assert_eq!(rebuilt, output);
+}
@@ -0,0 +1,94 @@
+use std::rc::{Rc, Weak};
+
+use buildstr::{BuildStr, Options};
+
+#[test]
+fn rc() {
+    let rc = Rc::new(5u8);
+    assert_eq!(rc.to_build_string(), "::std::rc::Rc::new(5u8)");
+    assert_eq!(
+        buildstr::render_with(&rc, &Options::default()),
+        "::std::rc::Rc::new(5u8)"
+    );
+}
+
+#[test]
+fn shared() {
+    #[derive(BuildStr)]
+    struct Node {
+        config: Rc<String>,
+    }
+
+    let config = Rc::new(String::from("config"));
+    let nodes = vec![
+        Node {
+            config: config.clone(),
+        },
+        Node {
+            config: config.clone(),
+        },
+        Node {
+            config: Rc::new(String::from("other")),
+        },
+    ];
+    assert_eq!(
+        nodes.to_build_string(),
+        "::std::vec::Vec::from_iter([Node{config: ::std::rc::Rc::new(::std::string::String::from(\"config\")),},Node{config: ::std::rc::Rc::new(::std::string::String::from(\"config\")),},Node{config: ::std::rc::Rc::new(::std::string::String::from(\"other\")),},])"
+    );
+    assert_eq!(
+        buildstr::render_with(&nodes, &Options::default()),
+        "{let __n0=::std::rc::Rc::new(::std::string::String::from(\"config\"));::std::vec::Vec::from_iter([Node{config: ::std::rc::Rc::clone(&__n0),},Node{config: ::std::rc::Rc::clone(&__n0),},Node{config: ::std::rc::Rc::new(::std::string::String::from(\"other\")),},])}"
+    );
+
+    let rebuilt = {
+        let __n0 = ::std::rc::Rc::new(::std::string::String::from("config"));
+        ::std::vec::Vec::from_iter([
+            Node {
+                config: ::std::rc::Rc::clone(&__n0),
+            },
+            Node {
+                config: ::std::rc::Rc::clone(&__n0),
+            },
+            Node {
+                config: ::std::rc::Rc::new(::std::string::String::from("other")),
+            },
+        ])
+    };
+    assert!(Rc::ptr_eq(&rebuilt[0].config, &rebuilt[1].config));
+
+    let mut unshared = Options::default();
+    unshared.share_pointers = false;
+    assert_eq!(
+        buildstr::render_with(&nodes, &unshared),
+        nodes.to_build_string()
+    );
+}
+
+#[test]
+fn nested() {
+    let inner = Rc::new(1u8);
+    let outer = Rc::new((inner.clone(), inner));
+    let value = (outer.clone(), outer);
+    assert_eq!(
+        buildstr::render_with(&value, &Options::default()),
+        "{let __n0=::std::rc::Rc::new(1u8);let __n1=::std::rc::Rc::new((::std::rc::Rc::clone(&__n0), ::std::rc::Rc::clone(&__n0)));(::std::rc::Rc::clone(&__n1), ::std::rc::Rc::clone(&__n1))}"
+    );
+}
+
+#[test]
+fn weak() {
+    let rc = Rc::new(5u8);
+    let weak = Rc::downgrade(&rc);
+    assert_eq!(
+        weak.to_build_string(),
+        "::std::rc::Rc::downgrade(&::std::rc::Rc::new(5u8))"
+    );
+    assert_eq!(
+        buildstr::render_with(&(rc, weak), &Options::default()),
+        "{let __n0=::std::rc::Rc::new(5u8);(::std::rc::Rc::clone(&__n0), ::std::rc::Rc::downgrade(&__n0))}"
+    );
+    assert_eq!(
+        Weak::<u8>::new().to_build_string(),
+        "::std::rc::Weak::new()"
+    );
+}
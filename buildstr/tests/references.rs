@@ -0,0 +1,80 @@
+use buildstr::{BuildStr, Options, References, RenderError, Style};
+
+#[derive(BuildStr, Debug, PartialEq)]
+struct Config {
+    name: &'static String,
+    tags: &'static [String],
+    limits: &'static [u16],
+    count: &'static mut u8,
+}
+
+fn options() -> Options {
+    let mut options = Options::default();
+    options.references = References::Leak;
+    options
+}
+
+fn config() -> Config {
+    Config {
+        name: Box::leak(Box::new(String::from("main"))),
+        tags: Box::leak(Box::new([String::from("a")])),
+        limits: &[1, 2],
+        count: Box::leak(Box::new(0)),
+    }
+}
+
+#[test]
+fn leak() {
+    let config = config();
+    assert_eq!(
+        config.to_build_string(),
+        "Config{name: &::std::string::String::from(\"main\"),tags: &[::std::string::String::from(\"a\"),],limits: &[1u16,2u16,],count: &mut 0u8,}"
+    );
+    assert_eq!(
+        buildstr::render_with(&config, &options()),
+        "Config{name: ::std::boxed::Box::leak(::std::boxed::Box::new(::std::string::String::from(\"main\"))),tags: ::std::boxed::Box::leak(::std::boxed::Box::new([::std::string::String::from(\"a\"),])),limits: &[1u16,2u16,],count: ::std::boxed::Box::leak(::std::boxed::Box::new(0u8)),}"
+    );
+
+    // The output can be stored where `'static` references are required
+    fn rebuilt() -> Config {
+        Config {
+            name: ::std::boxed::Box::leak(::std::boxed::Box::new(::std::string::String::from(
+                "main",
+            ))),
+            tags: ::std::boxed::Box::leak(::std::boxed::Box::new([::std::string::String::from(
+                "a",
+            )])),
+            limits: &[1u16, 2u16],
+            count: ::std::boxed::Box::leak(::std::boxed::Box::new(0u8)),
+        }
+    }
+    assert_eq!(rebuilt(), config);
+}
+
+#[test]
+fn idiomatic() {
+    let mut options = options();
+    options.style = Style::Idiomatic;
+    options.repeat = Some(8);
+    let names = vec![String::from("a"); 8];
+    assert_eq!(
+        buildstr::render_with(&&names[..], &options),
+        "Box::leak(Box::new(::core::array::from_fn::<_, 8, _>(|_| String::from(\"a\"))))"
+    );
+
+    // Shared values are bound to a variable, which can't be leaked
+    #[derive(BuildStr)]
+    struct Shared {
+        name: &'static std::rc::Rc<String>,
+        copy: std::rc::Rc<String>,
+    }
+
+    let name = Box::leak(Box::new(std::rc::Rc::new(String::new())));
+    let copy = name.clone();
+    assert_eq!(
+        buildstr::try_render_with(&Shared { name, copy }, &options),
+        Err(RenderError::Leaked {
+            path: vec!["references::idiomatic::Shared"]
+        })
+    );
+}
@@ -0,0 +1,92 @@
+use buildstr::{BuildStr, Options, Renderer};
+
+#[derive(BuildStr)]
+struct Id(u128);
+
+#[derive(BuildStr)]
+struct User {
+    id: Id,
+    name: String,
+    tags: Vec<String>,
+}
+
+#[derive(BuildStr)]
+struct Wrapper<T> {
+    value: T,
+    name: String,
+}
+
+fn user() -> User {
+    User {
+        id: Id(7),
+        name: "root".into(),
+        tags: vec!["admin".into()],
+    }
+}
+
+#[test]
+fn field() {
+    let mut renderer = Renderer::default();
+    renderer.register(|s: &String| format!("{s:?}.into()"));
+    assert_eq!(
+        renderer.render(&user()),
+        "User{id: Id(7u128,),name: \"root\".into(),tags: ::std::vec::Vec::from_iter([\"admin\".into(),]),}"
+    );
+    assert_eq!(renderer.render(&String::from("a")), "\"a\".into()");
+
+    let wrapper = Wrapper {
+        value: String::from("generic"),
+        name: "concrete".into(),
+    };
+    assert_eq!(
+        renderer.render(&wrapper),
+        "Wrapper{value: \"generic\".into(),name: \"concrete\".into(),}"
+    );
+}
+
+#[test]
+fn elements() {
+    let mut renderer = Renderer::default();
+    renderer.register(|b: &u8| format!("0x{b:02x}"));
+    assert_eq!(
+        renderer.render(&(vec![1u8, 2], Some(3u8))),
+        "(::std::vec::Vec::from_iter([0x01,0x02,]), ::core::option::Option::Some(0x03))"
+    );
+}
+
+#[test]
+fn derived() {
+    let mut renderer = Renderer::default();
+    renderer.register(|id: &Id| format!("Id::from_u128({})", id.0));
+    assert_eq!(
+        renderer.render(&vec![Some(Id(1)), None]),
+        "::std::vec::Vec::from_iter([::core::option::Option::Some(Id::from_u128(1)),::core::option::Option::None,])"
+    );
+
+    renderer.register(|_: &User| "ROOT".into());
+    assert_eq!(renderer.render(&(user(), 1u8)), "(ROOT, 1u8)");
+    assert!(renderer.unregister::<User>());
+    assert!(!renderer.unregister::<User>());
+    assert!(renderer
+        .render(&user())
+        .starts_with("User{id: Id::from_u128(7),"));
+}
+
+#[test]
+fn recursive() {
+    let mut renderer = Renderer::default();
+    renderer.register(|id: &Id| format!("wrap({})", id.to_build_string()));
+    assert_eq!(renderer.render(&Id(1)), "wrap(Id(1u128,))");
+}
+
+#[test]
+fn options() {
+    let shared = std::rc::Rc::new(Id(2));
+    let mut renderer = Renderer::new(Options::default());
+    renderer.register(|id: &Id| format!("ID_{}", id.0));
+    assert_eq!(
+        renderer.render(&(shared.clone(), shared)),
+        "{let __n0=::std::rc::Rc::new(ID_2);(::std::rc::Rc::clone(&__n0), ::std::rc::Rc::clone(&__n0))}"
+    );
+    assert_eq!(Id(3).to_build_string(), "Id(3u128,)");
+}
@@ -0,0 +1,92 @@
+use buildstr::{BuildStr, Options, Style, Suffixes};
+
+#[test]
+fn repeat() {
+    let mut options = Options::default();
+    options.repeat = Some(8);
+    let zeros = [0u8; 4096];
+    assert_eq!(buildstr::render_with(&zeros, &options), "[0u8; 4096]");
+    assert_eq!(buildstr::render_with(&&zeros[..], &options), "&[0u8; 4096]");
+    assert_eq!(
+        buildstr::render_with(&vec![(1u8, 'a'); 10], &options),
+        "::std::vec![(1u8, 'a'); 10]"
+    );
+    assert_eq!(::std::vec![(1u8, 'a'); 10], vec![(1u8, 'a'); 10]);
+
+    // Short arrays are kept as they are
+    assert_eq!(buildstr::render_with(&[0u8; 2], &options), "[0u8,0u8,]");
+    // Disabled by default
+    assert_eq!(
+        [0u16; 8].to_build_string(),
+        "[0u16,0u16,0u16,0u16,0u16,0u16,0u16,0u16,]"
+    );
+}
+
+#[test]
+fn not_copy() {
+    let mut options = Options::default();
+    options.repeat = Some(8);
+    let strings: [String; 8] = ::core::array::from_fn(|_| String::from("a"));
+    assert_eq!(
+        buildstr::render_with(&strings, &options),
+        "::core::array::from_fn::<_, 8, _>(|_| ::std::string::String::from(\"a\"))"
+    );
+    assert_eq!(
+        buildstr::render_with(&vec![String::new(); 8], &options),
+        "::std::vec::Vec::from_iter(::core::iter::repeat_with(|| ::std::string::String::from(\"\")).take(8))"
+    );
+    assert_eq!(
+        ::std::vec::Vec::from_iter(
+            ::core::iter::repeat_with(|| ::std::string::String::from("")).take(8)
+        ),
+        vec![String::new(); 8]
+    );
+}
+
+#[test]
+fn options() {
+    let mut options = Options::default();
+    options.style = Style::Idiomatic;
+    options.suffixes = Suffixes::Inferred;
+    options.repeat = Some(2);
+    assert_eq!(
+        buildstr::render_with(&vec![1u8; 3], &options),
+        "vec![1u8; 3]"
+    );
+    // The first element keeps its suffix
+    assert_eq!(
+        buildstr::render_with(&[[1u8, 2u8]; 3], &options),
+        "[[1u8, 2]; 3]"
+    );
+}
+
+#[test]
+fn run_length() {
+    let mut options = Options::default();
+    options.run_length = Some(4);
+    let mut values = vec![0u8; 100];
+    values[50] = 1;
+    values[51] = 2;
+    assert_eq!(
+        buildstr::render_with(&values, &options),
+        "::std::vec::Vec::from_iter(::core::iter::IntoIterator::into_iter([0u8; 50]).chain([1u8,2u8,]).chain([0u8; 48]))"
+    );
+    let rebuilt = ::std::vec::Vec::from_iter(
+        ::core::iter::IntoIterator::into_iter([0u8; 50])
+            .chain([1u8, 2u8])
+            .chain([0u8; 48]),
+    );
+    assert_eq!(rebuilt, values);
+
+    let mut array = [0u8; 10];
+    array[0] = 1;
+    assert_eq!(
+        buildstr::render_with(&array, &options),
+        "{let mut iter=::core::iter::IntoIterator::into_iter([1u8,]).chain([0u8; 9]);::core::array::from_fn::<_, 10, _>(|_| iter.next().unwrap())}"
+    );
+    let rebuilt = {
+        let mut iter = ::core::iter::IntoIterator::into_iter([1u8]).chain([0u8; 9]);
+        ::core::array::from_fn::<_, 10, _>(|_| iter.next().unwrap())
+    };
+    assert_eq!(rebuilt, array);
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use buildstr::{BuildStr, Options, Style};
+
+#[derive(BuildStr, Debug, Clone, PartialEq)]
+struct Point {
+    name: String,
+    x: Option<i32>,
+    y: Result<i32, ()>,
+}
+
+fn options(style: Style) -> Options {
+    let mut options = Options::default();
+    options.style = style;
+    options
+}
+
+#[test]
+fn ranges() {
+    let ranges = (1u8..2u8, 1u8.., 1u8..=2u8, ..2u8, ..=2u8, ..);
+    assert_eq!(
+        ranges.to_build_string(),
+        "(::core::ops::Range{start:1u8,end:2u8}, ::core::ops::RangeFrom{start:1u8}, ::core::ops::RangeInclusive::new(1u8,2u8), ::core::ops::RangeTo{end:2u8}, ::core::ops::RangeToInclusive{end:2u8}, ::core::ops::RangeFull)"
+    );
+    let rebuilt = (
+        ::core::ops::Range {
+            start: 1u8,
+            end: 2u8,
+        },
+        ::core::ops::RangeFrom { start: 1u8 },
+        ::core::ops::RangeInclusive::new(1u8, 2u8),
+        ::core::ops::RangeTo { end: 2u8 },
+        ::core::ops::RangeToInclusive { end: 2u8 },
+        ::core::ops::RangeFull,
+    );
+    assert_eq!(rebuilt, ranges);
+
+    assert_eq!(
+        buildstr::render_with(&ranges, &options(Style::Idiomatic)),
+        "(1u8..2u8, 1u8.., 1u8..=2u8, ..2u8, ..=2u8, ..)"
+    );
+    assert_eq!(
+        buildstr::render_with(&ranges, &options(Style::Minimal)),
+        "(1u8..2u8,1u8..,1u8..=2u8,..2u8,..=2u8,..)"
+    );
+
+    // Ranges are wrapped in parentheses when referenced
+    let range = 1u8..2u8;
+    assert_eq!(
+        buildstr::render_with(&&range, &options(Style::Idiomatic)),
+        "&(1u8..2u8)"
+    );
+    assert_eq!(&(1u8..2u8), &range);
+
+    // And when they are the bounds of another range
+    let nested = (1u8..2u8)..(3u8..4u8);
+    assert_eq!(
+        buildstr::render_with(&nested, &options(Style::Idiomatic)),
+        "(1u8..2u8)..(3u8..4u8)"
+    );
+    assert_eq!(
+        buildstr::render_with(&(..=(1u8..)), &options(Style::Minimal)),
+        "..=(1u8..)"
+    );
+    assert_eq!((1u8..2u8)..(3u8..4u8), nested);
+}
+
+#[test]
+fn canonical() {
+    let nested = vec![Some(Some(1u8)), Some(None), None];
+    assert_eq!(
+        buildstr::render_with(&nested, &options(Style::Canonical)),
+        "::std::vec::Vec::from_iter([::core::option::Option::Some(::core::option::Option::Some(1u8)),::core::option::Option::Some(::core::option::Option::None),::core::option::Option::None,])"
+    );
+    let rebuilt = ::std::vec::Vec::from_iter([
+        ::core::option::Option::Some(::core::option::Option::Some(1u8)),
+        ::core::option::Option::Some(::core::option::Option::None),
+        ::core::option::Option::None,
+    ]);
+    assert_eq!(rebuilt, nested);
+}
+
+#[test]
+fn idiomatic() {
+    let points = vec![Point {
+        name: String::from("origin"),
+        x: Some(0),
+        y: Err(()),
+    }];
+    assert_eq!(
+        buildstr::render_with(&points, &options(Style::Idiomatic)),
+        "vec![Point { name: String::from(\"origin\"), x: Some(0i32), y: Err(()) }]"
+    );
+    let rebuilt = vec![Point {
+        name: String::from("origin"),
+        x: Some(0i32),
+        y: Err(()),
+    }];
+    assert_eq!(rebuilt, points);
+
+    let points = vec![
+        Point {
+            name: String::from("a long name for the first point"),
+            x: None,
+            y: Ok(1),
+        };
+        2
+    ];
+    assert_eq!(
+        buildstr::render_with(&points, &options(Style::Idiomatic)),
+        r#"vec![
+    Point { name: String::from("a long name for the first point"), x: None, y: Ok(1i32) },
+    Point { name: String::from("a long name for the first point"), x: None, y: Ok(1i32) },
+]"#
+    );
+}
+
+#[test]
+fn minimal() {
+    let map = HashMap::from([(1u8, vec![String::from("a")])]);
+    assert_eq!(
+        buildstr::render_with(&map, &options(Style::Minimal)),
+        "{let mut map=::std::collections::HashMap::with_capacity_and_hasher(1,::std::collections::hash_map::RandomState::new());map.extend([(1u8,vec![String::from(\"a\"),]),]);map}"
+    );
+
+    // Calling `to_build_string` directly always uses the canonical style
+    assert_eq!(
+        Some(String::new()).to_build_string(),
+        "::core::option::Option::Some(::std::string::String::from(\"\"))"
+    );
+}
@@ -0,0 +1,134 @@
+use std::sync::{Arc, LazyLock, Mutex, OnceLock, RwLock, Weak};
+
+use buildstr::{BuildStr, Options, Poison, RenderError};
+
+#[test]
+fn arc() {
+    let arc = Arc::new(5u8);
+    assert_eq!(arc.to_build_string(), "::std::sync::Arc::new(5u8)");
+    let weak = Arc::downgrade(&arc);
+    assert_eq!(
+        buildstr::render_with(&(arc, weak), &Options::default()),
+        "{let __n0=::std::sync::Arc::new(5u8);(::std::sync::Arc::clone(&__n0), ::std::sync::Arc::downgrade(&__n0))}"
+    );
+    assert_eq!(
+        Weak::<u8>::new().to_build_string(),
+        "::std::sync::Weak::new()"
+    );
+}
+
+#[test]
+fn cyclic() {
+    #[derive(BuildStr)]
+    struct Node {
+        value: u8,
+        parent: Weak<Node>,
+    }
+
+    let node = Arc::new_cyclic(|parent| Node {
+        value: 1,
+        parent: parent.clone(),
+    });
+    assert!(buildstr::try_render_with(&node, &Options::default())
+        .unwrap()
+        .contains("::std::sync::Arc::new_cyclic("));
+}
+
+#[test]
+fn locks() {
+    let mutex = Mutex::new(vec![1u8]);
+    assert_eq!(
+        mutex.to_build_string(),
+        "::std::sync::Mutex::new(::std::vec::Vec::from_iter([1u8,]))"
+    );
+    let lock = RwLock::new(2u8);
+    assert_eq!(lock.to_build_string(), "::std::sync::RwLock::new(2u8)");
+
+    // A held lock can't be read
+    let guard = lock.write().unwrap();
+    assert!(matches!(
+        buildstr::try_render_with(&lock, &Options::default()),
+        Err(RenderError::Locked { path }) if path[0].ends_with("RwLock<u8>")
+    ));
+    // Rendering it directly panics
+    assert!(std::panic::catch_unwind(|| lock.to_build_string()).is_err());
+    drop(guard);
+
+    let once = OnceLock::new();
+    assert_eq!(once.to_build_string(), "::std::sync::OnceLock::new()");
+    once.set(3u8).unwrap();
+    assert_eq!(
+        once.to_build_string(),
+        "{let lock=::std::sync::OnceLock::new();let _=lock.set(3u8);lock}"
+    );
+    let rebuilt = {
+        let lock = ::std::sync::OnceLock::new();
+        let _ = lock.set(3u8);
+        lock
+    };
+    assert_eq!(rebuilt.get(), Some(&3));
+}
+
+#[test]
+fn lazy() {
+    // The value is initialized to render it
+    let lazy: LazyLock<Vec<u8>> = LazyLock::new(|| vec![1, 2]);
+    assert_eq!(
+        lazy.to_build_string(),
+        "::std::sync::LazyLock::new(|| ::std::vec::Vec::from_iter([1u8,2u8,]))"
+    );
+    let rebuilt: LazyLock<Vec<u8>> =
+        ::std::sync::LazyLock::new(|| ::std::vec::Vec::from_iter([1u8, 2u8]));
+    assert_eq!(*rebuilt, *lazy);
+}
+
+#[test]
+fn poison() {
+    let mutex = Arc::new(Mutex::new(1u8));
+    let clone = mutex.clone();
+    let _ = std::thread::spawn(move || {
+        let _guard = clone.lock().unwrap();
+        panic!("poison the lock");
+    })
+    .join();
+    assert!(mutex.is_poisoned());
+
+    assert_eq!(
+        mutex.to_build_string(),
+        "::std::sync::Arc::new(::std::sync::Mutex::new(1u8))"
+    );
+    let mut options = Options::default();
+    options.poison = Poison::Fail;
+    let error = buildstr::try_render_with(&mutex, &options).unwrap_err();
+    assert!(matches!(error, RenderError::Poisoned { ref path } if path.len() == 2));
+    assert!(error
+        .to_string()
+        .starts_with("poisoned lock found while rendering: "));
+}
+
+#[test]
+fn std() {
+    use std::sync::mpsc::{RecvError, RecvTimeoutError, TryRecvError};
+    use std::sync::{Condvar, Once};
+
+    assert_eq!(
+        Condvar::new().to_build_string(),
+        "::std::sync::Condvar::new()"
+    );
+    let once = Once::new();
+    assert_eq!(once.to_build_string(), "::std::sync::Once::new()");
+    once.call_once(|| {});
+    assert_eq!(
+        once.to_build_string(),
+        "{let once=::std::sync::Once::new();once.call_once(||{});once}"
+    );
+    assert_eq!(RecvError.to_build_string(), "::std::sync::mpsc::RecvError");
+    assert_eq!(
+        TryRecvError::Disconnected.to_build_string(),
+        "::std::sync::mpsc::TryRecvError::Disconnected"
+    );
+    assert_eq!(
+        RecvTimeoutError::Timeout.to_build_string(),
+        "::std::sync::mpsc::RecvTimeoutError::Timeout"
+    );
+}
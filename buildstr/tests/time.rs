@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use buildstr::{BuildStr, Instants, Options, RenderError};
+
+#[test]
+fn system_time() {
+    let time = UNIX_EPOCH + Duration::new(1_700_000_000, 5);
+    assert_eq!(
+        time.to_build_string(),
+        "::std::time::UNIX_EPOCH + ::core::time::Duration::new(1700000000, 5)"
+    );
+    assert_eq!(
+        ::std::time::UNIX_EPOCH + ::core::time::Duration::new(1700000000, 5),
+        time
+    );
+
+    let before = UNIX_EPOCH - Duration::from_secs(60);
+    assert_eq!(
+        before.to_build_string(),
+        "::std::time::UNIX_EPOCH - ::core::time::Duration::new(60, 0)"
+    );
+}
+
+#[test]
+fn instant() {
+    let now = Instant::now();
+    let earlier = now.checked_sub(Duration::from_secs(5)).unwrap();
+    let later = now + Duration::from_secs(5);
+    let rendered = buildstr::render_with(&(earlier, later), &Options::default());
+    assert!(rendered.starts_with("(::std::time::Instant::now() - ::core::time::Duration::new(5, "));
+    assert!(rendered.contains(", ::std::time::Instant::now() + ::core::time::Duration::new("));
+
+    // Rendering them directly also measures them from the same moment
+    let rendered = (earlier, earlier).to_build_string();
+    let inner = &rendered[1..rendered.len() - 1];
+    let (first, second) = inner.split_at((inner.len() - 2) / 2);
+    assert_eq!(first, &second[2..]);
+
+    let mut options = Options::default();
+    options.instants = Instants::Fail;
+    assert!(matches!(
+        buildstr::try_render_with(&(1u8, now), &options),
+        Err(RenderError::Instant { path }) if path.len() == 1
+    ));
+}
+
+#[test]
+fn errors() {
+    let error = UNIX_EPOCH
+        .duration_since(UNIX_EPOCH + Duration::from_secs(2))
+        .unwrap_err();
+    assert_eq!(
+        error.to_build_string(),
+        "::std::time::UNIX_EPOCH.duration_since(::std::time::UNIX_EPOCH + ::core::time::Duration::new(2, 0)).unwrap_err()"
+    );
+    let rebuilt = ::std::time::UNIX_EPOCH
+        .duration_since(::std::time::UNIX_EPOCH + ::core::time::Duration::new(2, 0))
+        .unwrap_err();
+    assert_eq!(rebuilt.duration(), error.duration());
+
+    let negative = Duration::try_from_secs_f32(-2.0).unwrap_err();
+    assert_eq!(
+        negative.to_build_string(),
+        "::core::time::Duration::try_from_secs_f64(-1.0).unwrap_err()"
+    );
+    let overflow = Duration::try_from_secs_f64(f64::INFINITY).unwrap_err();
+    assert_eq!(
+        overflow.to_build_string(),
+        "::core::time::Duration::try_from_secs_f64(::core::primitive::f64::NAN).unwrap_err()"
+    );
+    assert_eq!(
+        ::core::time::Duration::try_from_secs_f64(::core::primitive::f64::NAN).unwrap_err(),
+        overflow
+    );
+}
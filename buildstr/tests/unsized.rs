@@ -0,0 +1,97 @@
+use std::borrow::Cow;
+use std::ffi::{CStr, OsStr};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use buildstr::{BuildStr, Options};
+
+#[test]
+fn boxed() {
+    let s: Box<str> = Box::from("name");
+    assert_eq!(s.to_build_string(), "Box::<str>::from(\"name\")");
+    let slice: Box<[u16]> = Box::from([1u16, 2u16]);
+    assert_eq!(slice.to_build_string(), "Box::<[_]>::from([1u16,2u16,])");
+    assert_eq!(Box::<[_]>::from([1u16, 2u16]), slice);
+    let bytes: Box<[u8]> = Box::from(*b"ab");
+    assert_eq!(bytes.to_build_string(), "Box::<[_]>::from([97u8,98u8,])");
+    assert_eq!(Box::<[_]>::from([97u8, 98u8]), bytes);
+
+    let c: Box<CStr> = Box::from(c"ab");
+    assert_eq!(
+        c.to_build_string(),
+        "Box::<::std::ffi::CStr>::from(::std::ffi::CStr::from_bytes_with_nul(&[97u8,98u8,0u8,]).unwrap())"
+    );
+    let path: Box<Path> = Box::from(Path::new("src/lib.rs"));
+    assert_eq!(
+        path.to_build_string(),
+        "Box::<::std::path::Path>::from(::std::path::Path::new(\"src/lib.rs\"))"
+    );
+    let os: Box<OsStr> = Box::from(OsStr::new("a"));
+    assert_eq!(
+        os.to_build_string(),
+        "Box::<::std::ffi::OsStr>::from(::std::ffi::OsStr::new(\"a\"))"
+    );
+}
+
+#[test]
+fn shared() {
+    let name: Rc<str> = Rc::from("name");
+    assert_eq!(
+        name.to_build_string(),
+        "::std::rc::Rc::<str>::from(\"name\")"
+    );
+    assert_eq!(
+        buildstr::render_with(&(name.clone(), name), &Options::default()),
+        "{let __n0=::std::rc::Rc::<str>::from(\"name\");(::std::rc::Rc::clone(&__n0), ::std::rc::Rc::clone(&__n0))}"
+    );
+    let rebuilt = {
+        let __n0 = ::std::rc::Rc::<str>::from("name");
+        (::std::rc::Rc::clone(&__n0), ::std::rc::Rc::clone(&__n0))
+    };
+    assert!(Rc::ptr_eq(&rebuilt.0, &rebuilt.1));
+
+    let items: Arc<[Option<u8>]> = Arc::from([Some(1u8), None]);
+    assert_eq!(
+        items.to_build_string(),
+        "::std::sync::Arc::<[_]>::from([::core::option::Option::Some(1u8),::core::option::Option::None,])"
+    );
+    let path: Arc<Path> = Arc::from(Path::new("a"));
+    assert_eq!(
+        path.to_build_string(),
+        "::std::sync::Arc::<::std::path::Path>::from(::std::path::Path::new(\"a\"))"
+    );
+    let rc: Rc<[u16]> = Rc::from([3u16]);
+    assert_eq!(rc.to_build_string(), "::std::rc::Rc::<[_]>::from([3u16,])");
+}
+
+#[test]
+fn cow() {
+    let borrowed: Cow<str> = Cow::Borrowed("a");
+    assert_eq!(
+        borrowed.to_build_string(),
+        "::std::borrow::Cow::Borrowed::<str>(\"a\")"
+    );
+    let owned: Cow<[u16]> = Cow::Owned(vec![1]);
+    assert_eq!(
+        owned.to_build_string(),
+        "::std::borrow::Cow::Owned::<[u16]>(::std::vec::Vec::from_iter([1u16,]))"
+    );
+    let path: Cow<Path> = Cow::Borrowed(Path::new("a"));
+    assert_eq!(
+        path.to_build_string(),
+        "::std::borrow::Cow::Borrowed(::std::path::Path::new(\"a\"))"
+    );
+    let rebuilt: Cow<Path> = ::std::borrow::Cow::Borrowed(::std::path::Path::new("a"));
+    assert_eq!(rebuilt, path);
+    let strings: Cow<[String]> = Cow::Owned(vec![String::new()]);
+    assert_eq!(
+        strings.to_build_string(),
+        "::std::borrow::Cow::Owned(::std::vec::Vec::from_iter([::std::string::String::from(\"\"),]))"
+    );
+    let rebuilt: Cow<[String]> =
+        ::std::borrow::Cow::Owned(::std::vec::Vec::from_iter([::std::string::String::from(
+            "",
+        )]));
+    assert_eq!(rebuilt, strings);
+}
@@ -1,7 +1,9 @@
 use proc_macro::TokenStream;
 
+#[cfg(any(feature = "derive", feature = "compress"))]
+use quote::quote;
 #[cfg(feature = "derive")]
-use quote::{quote, quote_spanned};
+use quote::quote_spanned;
 #[cfg(feature = "derive")]
 use syn::spanned::Spanned;
 
@@ -47,15 +49,31 @@ pub fn buildstr(input: TokenStream) -> TokenStream {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let body = match input.data {
-        syn::Data::Struct(ref s) => parse_struct(s, &name),
-        syn::Data::Enum(e) => parse_enum(e, &name),
+        syn::Data::Struct(ref s) => parse_struct(s, &name, &generics),
+        syn::Data::Enum(e) => parse_enum(e, &name, &generics),
         syn::Data::Union(_) => panic!("Unions are not supported"),
     };
+    // Overrides are keyed by `TypeId`, so they can only be used on `'static` types
+    let body = if generics.params.is_empty() {
+        quote! {
+            buildstr::__private::render_static::<Self>(self, || {
+                buildstr::__private::nested(::std::any::type_name::<Self>(), || {
+                    #body
+                })
+            })
+        }
+    } else {
+        quote! {
+            buildstr::__private::nested(::std::any::type_name::<Self>(), || {
+                #body
+            })
+        }
+    };
 
     quote! {
         #[allow(clippy::needless_borrow)]
         impl #impl_generics BuildStr for #name #ty_generics #where_clause {
-            fn to_build_string(&self) -> String {
+            fn to_build_expr(&self) -> buildstr::Expr {
                 #body
             }
         }
@@ -63,8 +81,211 @@ pub fn buildstr(input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Derives the `FromBuildBytes` trait for a `struct` or `enum`, to embed it compressed.
+///
+/// All the fields must implement `FromBuildBytes`. The variants of enums are written as their index.
+///
+/// # Examples
+/// ```
+/// use buildstr::FromBuildBytes;
+///
+/// #[derive(FromBuildBytes, Debug, PartialEq)]
+/// enum Token {
+///     Word(String),
+///     Number { value: i64 },
+///     End,
+/// }
+///
+/// let tokens = vec![Token::Word("a".into()), Token::Number { value: 1 }, Token::End];
+/// let bytes = buildstr::compress::compress(&tokens);
+/// assert_eq!(buildstr::compress::decompress::<Vec<Token>>(&bytes), tokens);
+/// ```
+#[cfg(feature = "compress")]
+#[proc_macro_derive(FromBuildBytes)]
+pub fn from_build_bytes(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    let name = input.ident;
+    let mut generics = input.generics;
+    for param in &mut generics.params {
+        if let syn::GenericParam::Type(ref mut type_param) = *param {
+            type_param
+                .bounds
+                .push(syn::parse_quote!(buildstr::FromBuildBytes));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let (write, read) = match input.data {
+        syn::Data::Struct(s) => {
+            let (pattern, write, read) = bytes_fields(&s.fields, &quote!(Self));
+            (
+                quote! {
+                    let #pattern = self;
+                    #write
+                },
+                quote!(::core::option::Option::Some(#read)),
+            )
+        }
+        syn::Data::Enum(e) => {
+            let (write, read): (Vec<_>, Vec<_>) = e
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let index = u32::try_from(i).expect("too many variants");
+                    let variant = &v.ident;
+                    let (pattern, write, read) = bytes_fields(&v.fields, &quote!(Self::#variant));
+                    (
+                        quote! {
+                            #pattern => {
+                                buildstr::FromBuildBytes::to_build_bytes(&#index, out);
+                                #write
+                            }
+                        },
+                        quote!(#index => ::core::option::Option::Some(#read),),
+                    )
+                })
+                .unzip();
+            (
+                quote! {
+                    match self {
+                        #(#write)*
+                    }
+                },
+                quote! {
+                    match <u32 as buildstr::FromBuildBytes>::from_build_bytes(bytes)? {
+                        #(#read)*
+                        _ => ::core::option::Option::None,
+                    }
+                },
+            )
+        }
+        syn::Data::Union(_) => panic!("Unions are not supported"),
+    };
+
+    quote! {
+        #[allow(unused_variables)]
+        impl #impl_generics buildstr::FromBuildBytes for #name #ty_generics #where_clause {
+            fn to_build_bytes(&self, out: &mut ::std::vec::Vec<u8>) {
+                #write
+            }
+            fn from_build_bytes(bytes: &mut &[u8]) -> ::core::option::Option<Self> {
+                #read
+            }
+        }
+    }
+    .into()
+}
+
+/// Gets the pattern binding the `fields` of `path`, the code writing them, and the expression reading them.
+#[cfg(feature = "compress")]
+fn bytes_fields(
+    fields: &syn::Fields,
+    path: &proc_macro2::TokenStream,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let names = (0..fields.len())
+        .map(|i| quote::format_ident!("_{i}"))
+        .collect::<Vec<_>>();
+    let write = quote! {
+        #(buildstr::FromBuildBytes::to_build_bytes(#names, out);)*
+    };
+    let value = quote!(buildstr::FromBuildBytes::from_build_bytes(bytes)?);
+    match fields {
+        syn::Fields::Named(fields) => {
+            let fields = fields.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
+            (
+                quote!(#path { #(#fields: #names),* }),
+                write,
+                quote!(#path { #(#fields: #value),* }),
+            )
+        }
+        syn::Fields::Unnamed(_) => {
+            let values = names.iter().map(|_| &value);
+            (
+                quote!(#path(#(#names),*)),
+                write,
+                quote!(#path(#(#values),*)),
+            )
+        }
+        syn::Fields::Unit => (quote!(#path), write, quote!(#path)),
+    }
+}
+
+/// Returns `true` if `ty` is `'static`, as it doesn't contain lifetimes or generic parameters.
+#[cfg(feature = "derive")]
+fn is_static(ty: &syn::Type, generics: &syn::Generics) -> bool {
+    fn check(tokens: proc_macro2::TokenStream, generics: &syn::Generics) -> bool {
+        let mut tokens = tokens.into_iter();
+        while let Some(token) = tokens.next() {
+            let valid = match token {
+                proc_macro2::TokenTree::Group(group) => check(group.stream(), generics),
+                proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                    matches!(tokens.next(), Some(proc_macro2::TokenTree::Ident(i)) if i == "static")
+                }
+                proc_macro2::TokenTree::Ident(ident) => {
+                    ident != "Self" && generics.type_params().all(|p| p.ident != ident)
+                }
+                _ => true,
+            };
+            if !valid {
+                return false;
+            }
+        }
+        true
+    }
+    check(quote::ToTokens::to_token_stream(ty), generics)
+}
+
+/// Returns `true` if `ty` doesn't contain the generic type parameters, so it's fixed by the field.
+#[cfg(feature = "derive")]
+fn is_concrete(ty: &syn::Type, generics: &syn::Generics) -> bool {
+    fn check(tokens: proc_macro2::TokenStream, generics: &syn::Generics) -> bool {
+        tokens.into_iter().all(|token| match token {
+            proc_macro2::TokenTree::Group(group) => check(group.stream(), generics),
+            proc_macro2::TokenTree::Ident(ident) => generics.type_params().all(|p| p.ident != ident),
+            _ => true,
+        })
+    }
+    check(quote::ToTokens::to_token_stream(ty), generics)
+}
+
+/// Renders a field of type `ty`, using the overrides of its type if it's `'static`.
 #[cfg(feature = "derive")]
-fn parse_enum(e: syn::DataEnum, name: &syn::Ident) -> proc_macro2::TokenStream {
+fn field(value: proc_macro2::TokenStream, ty: &syn::Type, generics: &syn::Generics) -> proc_macro2::TokenStream {
+    let render = if is_static(ty, generics) {
+        quote_spanned! {ty.span()=>
+            buildstr::__private::render_static::<#ty>(#value, || #value.to_build_expr())
+        }
+    } else {
+        quote_spanned! {ty.span()=>
+            #value.to_build_expr()
+        }
+    };
+    // Fields of a generic type are only fixed if the derived value is
+    let render = if is_concrete(ty, generics) {
+        quote_spanned! {ty.span()=>
+            buildstr::__private::typed(true, || #render)
+        }
+    } else {
+        render
+    };
+    quote_spanned! {ty.span()=>
+        buildstr::__private::dedup(#value, #render)
+    }
+}
+
+/// Renders the path of the type `name`, qualified with its module if requested by the options.
+#[cfg(feature = "derive")]
+fn type_path(name: &syn::Ident) -> proc_macro2::TokenStream {
+    quote! {
+        buildstr::__private::type_path(module_path!(), stringify!(#name), ::std::any::type_name::<Self>())
+    }
+}
+
+#[cfg(feature = "derive")]
+fn parse_enum(e: syn::DataEnum, name: &syn::Ident, generics: &syn::Generics) -> proc_macro2::TokenStream {
+    let path = type_path(name);
     let variants = e.variants.iter().map(|v| {
         let variant = &v.ident;
         match v.fields {
@@ -72,38 +293,30 @@ fn parse_enum(e: syn::DataEnum, name: &syn::Ident) -> proc_macro2::TokenStream {
                 let fields = fields.named.iter().map(|field| {
                     &field.ident
                 }).collect::<Vec<_>>();
+                let values = v.fields.iter().zip(&fields).map(|(f, name)| field(quote!(#name), &f.ty, generics));
 
                 quote! {
-                    #name::#variant { #(#fields),* } => {
-                        let mut s = format!("{}::{}{{", stringify!(#name), stringify!(#variant));
-                        #(
-                            let f = format!("{}: {},", stringify!(#fields), #fields.to_build_string());
-                            s.push_str(&f);
-                        )*
-                        s.push('}');
-                        s
-                    }
+                    #name::#variant { #(#fields),* } => buildstr::Expr::StructLit {
+                        path: buildstr::Path::parse(&format!("{}::{}", #path, stringify!(#variant))),
+                        fields: ::std::vec![#((::std::string::String::from(stringify!(#fields)), #values)),*],
+                        layout: buildstr::Layout::Terminated,
+                    },
                 }
             },
             syn::Fields::Unnamed(ref fields) => {
                 let fields = (0..fields.unnamed.len()).map(|i| {
                     syn::parse_str::<syn::Ident>(&format!("_{i}")).unwrap()
                 }).collect::<Vec<_>>();
+                let values = v.fields.iter().zip(&fields).map(|(f, name)| field(quote!(#name), &f.ty, generics));
 
                 quote! {
-                    #name::#variant( #(#fields),* ) => {
-                        let mut s = format!("{}::{}(", stringify!(#name), stringify!(#variant));
-                        #(
-                            s.push_str(&#fields.to_build_string());
-                            s.push(',');
-                        )*
-                        s.push(')');
-                        s
-                    }
+                    #name::#variant( #(#fields),* ) => buildstr::Expr::path(&format!("{}::{}", #path, stringify!(#variant)))
+                        .call(::std::vec![#(#values),*])
+                        .with_layout(buildstr::Layout::Terminated),
                 }
             }
             syn::Fields::Unit => quote! {
-                #name::#variant => format!("{}::{}", stringify!(#name), stringify!(#variant)),
+                #name::#variant => buildstr::Expr::path(&format!("{}::{}", #path, stringify!(#variant))),
             },
         }
     });
@@ -115,42 +328,43 @@ fn parse_enum(e: syn::DataEnum, name: &syn::Ident) -> proc_macro2::TokenStream {
 }
 
 #[cfg(feature = "derive")]
-fn parse_struct(s: &syn::DataStruct, name: &syn::Ident) -> proc_macro2::TokenStream {
+fn parse_struct(s: &syn::DataStruct, name: &syn::Ident, generics: &syn::Generics) -> proc_macro2::TokenStream {
+    let path = type_path(name);
     match s.fields {
         syn::Fields::Named(ref fields) => {
             let fields = fields.named.iter().map(|field| {
                 let name = &field.ident;
+                let value = self::field(quote!((&self.#name)), &field.ty, generics);
                 quote_spanned! {field.span()=>
-                    format!("{}: {},", stringify!(#name), (&self.#name).to_build_string())
+                    (::std::string::String::from(stringify!(#name)), #value)
                 }
             });
 
             quote! {
-                let mut s = format!("{}{{", stringify!(#name));
-                #(s.push_str(&#fields);)*
-                s.push('}');
-                s
+                buildstr::Expr::StructLit {
+                    path: buildstr::Path::parse(&#path),
+                    fields: ::std::vec![#(#fields),*],
+                    layout: buildstr::Layout::Terminated,
+                }
             }
         }
         syn::Fields::Unnamed(ref fields) => {
             let fields = fields.unnamed.iter().enumerate().map(|(i, field)| {
                 let name = syn::Index::from(i);
+                let value = self::field(quote!((&self.#name)), &field.ty, generics);
                 quote_spanned! {field.span()=>
-                    format!("{},", (&self.#name).to_build_string())
+                    #value
                 }
             });
             quote! {
-                let mut s = format!("{}(", stringify!(#name));
-                #(s.push_str(&#fields);)*
-                s.push(')');
-                s
-            }
-        }
-        syn::Fields::Unit => {
-            quote! {
-                stringify!(#name).into()
+                buildstr::Expr::path(&#path)
+                    .call(::std::vec![#(#fields),*])
+                    .with_layout(buildstr::Layout::Terminated)
             }
         }
+        syn::Fields::Unit => quote! {
+            buildstr::Expr::path(&#path)
+        },
     }
 }
 
@@ -168,8 +382,8 @@ fn parse_struct(s: &syn::DataStruct, name: &syn::Ident) -> proc_macro2::TokenStr
 ///
 /// // num_bigint::BigInt does not implement `BuildStr`, so we need to implement it manually
 /// impl BuildStr2 for num_bigint::BigInt {
-///     fn to_build_string(&self) -> String {
-///         format!("num_bigint::BigInt::from_str({})", self.to_string())
+///     fn to_build_expr(&self) -> buildstr::Expr {
+///         buildstr::Expr::Verbatim(format!("num_bigint::BigInt::from_str({})", self.to_string()))
 ///     }
 /// }
 ///
@@ -246,7 +460,49 @@ pub fn impl_buildstr(input: TokenStream) -> TokenStream {
             /// };
             /// assert_eq!((&person).to_build_string(), "Person{name: ::std::string::String::from(\"John\"),age: 30u8,balance: 1000f64,}");
             /// ```
-            fn to_build_string(&self) -> String;
+            fn to_build_string(&self) -> String {
+                let _now = buildstr::__private::context::hold_now();
+                self.to_build_expr().to_string()
+            }
+
+            /// Gets the syntax tree of the builder of a type, to inspect, transform or print it.
+            ///
+            /// Implementations must provide this method, and [`to_build_string`](Self::to_build_string) prints it.
+            /// Builders that are only known as a string can be kept as an [`Expr::Verbatim`](buildstr::Expr::Verbatim).
+            ///
+            /// # Examples
+            /// ```
+            /// use buildstr::{BuildStr, Expr};
+            ///
+            /// let expr = Some(1u8).to_build_expr();
+            /// assert!(matches!(expr, Expr::Call { .. }));
+            /// assert_eq!(expr.to_string(), "::core::option::Option::Some(1u8)");
+            /// ```
+            fn to_build_expr(&self) -> buildstr::Expr;
+
+            /// Gets the trait object type of the value, like `dyn Shape`, if it's rendered through [`DynBuildStr`],
+            /// so pointers to it are coerced back to that type.
+            #[doc(hidden)]
+            fn __dyn_type(&self) -> Option<&'static str> {
+                None
+            }
+
+            /// Gets the path of the unsized type of the value, like `::std::ffi::CStr`, if it's rendered as a reference to it,
+            /// so references to it aren't borrowed again and pointers to it are built from that reference.
+            #[doc(hidden)]
+            fn __unsized_type(&self) -> Option<&'static str> {
+                None
+            }
+
+            /// Gets the elements of `slice` as bytes if `Self` is `u8`, so sequences of them can be written as byte strings.
+            #[doc(hidden)]
+            #[allow(unused_variables)]
+            fn __bytes(slice: &[Self]) -> Option<&[u8]>
+            where
+                Self: Sized,
+            {
+                None
+            }
 
             $to_build_tokens
         }
@@ -261,7 +517,7 @@ pub fn impl_buildstr(input: TokenStream) -> TokenStream {
             ///
             /// For more information see [`to_build_string`](Self::to_build_string).
             fn to_build_tokens(&self) -> buildstr::__private::TokenStream {
-                buildstr::__private::__str_to_tokens(self.to_build_string())
+                self.to_build_expr().to_tokens()
             }
         };
         out = out.replace("$to_build_tokens", to);
@@ -289,7 +545,8 @@ pub fn impl_buildstr(input: TokenStream) -> TokenStream {
             array,
             vec,
             tuple,
-            reference
+            reference,
+            r#dyn
         ]
         "extra" => [
             borrow,
@@ -301,6 +558,7 @@ pub fn impl_buildstr(input: TokenStream) -> TokenStream {
             fmt,
             future,
             hash,
+            io,
             marker,
             mem,
             net,
@@ -308,7 +566,8 @@ pub fn impl_buildstr(input: TokenStream) -> TokenStream {
             ops,
             panic,
             pin,
-            ffi
+            ffi,
+            sync
         ]
     }
 
@@ -336,7 +595,7 @@ fn pretty() -> &'static str {
         }
         impl<T: BuildStr> Pretty for T {
             fn to_pretty_build_string(&self) -> String {
-                buildstr::__private::__pretty((&self).to_build_string())
+                (&self).to_build_expr().to_pretty_string()
             }
         }
     }
@@ -344,12 +603,53 @@ fn pretty() -> &'static str {
 
 impls! {
 
+fn r#dyn() {
+    /// Object-safe version of [`BuildStr`], to render trait objects.
+    ///
+    /// It's implemented for all the types that implement [`BuildStr`].<br>
+    /// A trait that requires it can implement [`BuildStr`] for its trait objects with
+    /// [`impl_dyn_buildstr!`](crate::impl_dyn_buildstr), so `Box<dyn Trait>`, `Rc<dyn Trait>`, `Arc<dyn Trait>`
+    /// and `&dyn Trait` are rendered with the concrete type, and coerced back to the trait object.
+    ///
+    /// # Examples
+    /// ```
+    /// use buildstr::{BuildStr, DynBuildStr};
+    ///
+    /// trait Shape: DynBuildStr {}
+    /// buildstr::impl_dyn_buildstr!(dyn Shape);
+    ///
+    /// #[derive(BuildStr)]
+    /// struct Circle(u8);
+    /// impl Shape for Circle {}
+    ///
+    /// let shape: Box<dyn Shape> = Box::new(Circle(1));
+    /// assert_eq!(shape.to_build_string(), "Box::new(Circle(1u8,)) as Box<dyn Shape>");
+    /// ```
+    pub trait DynBuildStr {
+        /// Gets a string representation of the builder of the concrete type, see [`BuildStr::to_build_string`].
+        fn dyn_build_string(&self) -> String;
+        /// Gets the expression that builds the concrete type, see [`BuildStr::to_build_expr`].
+        fn dyn_build_expr(&self) -> buildstr::Expr;
+    }
+    impl<T: BuildStr> DynBuildStr for T {
+        fn dyn_build_string(&self) -> String {
+            self.to_build_string()
+        }
+        fn dyn_build_expr(&self) -> buildstr::Expr {
+            self.to_build_expr()
+        }
+    }
+}
+
 fn option() {
     impl<T: BuildStr> BuildStr for Option<T> {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let idiomatic = buildstr::__private::idiomatic();
             match self {
-                Some(s) => format!("::core::option::Some({})", s.to_build_string()),
-                None => ::std::string::String::from("::core::option::None"),
+                Some(s) if idiomatic => buildstr::Expr::path("Some").call([s.to_build_expr()]),
+                Some(s) => buildstr::Expr::path("::core::option::Option::Some").call([s.to_build_expr()]),
+                None if idiomatic => buildstr::Expr::path("None"),
+                None => buildstr::Expr::path("::core::option::Option::None"),
             }
         }
     }
@@ -357,260 +657,412 @@ fn option() {
 
 fn result() {
     impl<T, E> BuildStr for Result<T, E> where T: BuildStr, E: BuildStr {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let idiomatic = buildstr::__private::idiomatic();
             match self {
-                Ok(s) => format!("::core::result::Result::Ok({})", s.to_build_string()),
-                Err(s) => format!("::core::result::Result::Err({})", s.to_build_string()),
+                Ok(s) if idiomatic => buildstr::Expr::path("Ok").call([s.to_build_expr()]),
+                Ok(s) => buildstr::Expr::path("::core::result::Result::Ok").call([s.to_build_expr()]),
+                Err(s) if idiomatic => buildstr::Expr::path("Err").call([s.to_build_expr()]),
+                Err(s) => buildstr::Expr::path("::core::result::Result::Err").call([s.to_build_expr()]),
             }
         }
     }
 }
 
 fn r#box() {
-    impl<T: BuildStr> BuildStr for Box<T> {
-        fn to_build_string(&self) -> String {
-            format!("Box::new({})", self.as_ref().to_build_string())
+    impl<T: BuildStr + ?Sized> BuildStr for Box<T> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            if let Some(ty) = self.as_ref().__unsized_type() {
+                let inner = self.as_ref().to_build_expr();
+                return buildstr::Expr::path(&format!("Box::<{ty}>::from")).call([inner]);
+            }
+            let inner = buildstr::Expr::path("Box::new").call([self.as_ref().to_build_expr()]);
+            match self.as_ref().__dyn_type() {
+                Some(ty) => inner.cast(format!("Box<{ty}>")),
+                None => inner,
+            }
+        }
+    }
+    impl<T: BuildStr> BuildStr for Box<[T]> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let array = buildstr::__private::byte_sequence(buildstr::__private::Sequence::Array, T::__bytes(&self[..])).unwrap_or_else(|| {
+                let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+                buildstr::__private::sequence(buildstr::__private::Sequence::Array, elements)
+            });
+            buildstr::Expr::path("Box::<[_]>::from").call([array])
         }
     }
 }
 
 fn rc() {
-    impl<T: BuildStr> BuildStr for ::std::rc::Rc<T> {
-        fn to_build_string(&self) -> String {
-            format!("Rc::new({})", self.as_ref().to_build_string())
+    impl<T: BuildStr + ?Sized> BuildStr for ::std::rc::Rc<T> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let name = ::std::any::type_name::<Self>();
+            if let Some(ty) = self.as_ref().__unsized_type() {
+                return buildstr::__private::shared_from(::std::rc::Rc::as_ptr(self), name, "::std::rc::Rc", ty, || self.as_ref().to_build_expr());
+            }
+            match self.as_ref().__dyn_type() {
+                Some(ty) => buildstr::__private::shared_dyn(::std::rc::Rc::as_ptr(self), name, "::std::rc::Rc", ty, || self.as_ref().to_build_expr()),
+                None => buildstr::__private::shared(::std::rc::Rc::as_ptr(self), name, "::std::rc::Rc", || self.as_ref().to_build_expr()),
+            }
+        }
+    }
+    impl<T: BuildStr> BuildStr for ::std::rc::Rc<[T]> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let name = ::std::any::type_name::<Self>();
+            buildstr::__private::shared_from(::std::rc::Rc::as_ptr(self), name, "::std::rc::Rc", "[_]", || {
+                buildstr::__private::byte_sequence(buildstr::__private::Sequence::Array, T::__bytes(&self[..])).unwrap_or_else(|| {
+                    let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+                    buildstr::__private::sequence(buildstr::__private::Sequence::Array, elements)
+                })
+            })
         }
     }
     impl<T: BuildStr> BuildStr for ::std::rc::Weak<T> {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
             match self.upgrade() {
-                Some(s) => format!(
-                    "::std::rc::Rc::downgrade(&::std::rc::Rc::new({}))",
-                    s.as_ref().to_build_string()
+                Some(s) => {
+                    let name = ::std::any::type_name::<::std::rc::Rc<T>>();
+                    buildstr::__private::weak(::std::rc::Rc::as_ptr(&s), name, "::std::rc::Rc", "::std::rc::Weak", || s.as_ref().to_build_expr())
+                }
+                None => buildstr::Expr::path("::std::rc::Weak::new").call([]),
+            }
+        }
+    }
+}
+
+fn io() {
+    impl<T: BuildStr> BuildStr for ::std::io::Cursor<T> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let inner = buildstr::Expr::path("::std::io::Cursor::new").call([self.get_ref().to_build_expr()]);
+            match self.position() {
+                0 => inner,
+                position => buildstr::Expr::block(
+                    vec![
+                        buildstr::Stmt::Let { mutable: true, name: "cursor".into(), ty: None, init: inner },
+                        buildstr::Stmt::Expr(buildstr::Expr::path("cursor").method("set_position", [buildstr::Expr::Lit(format!("{position}u64"))])),
+                    ],
+                    buildstr::Expr::path("cursor"),
                 ),
-                None => ::std::string::String::from("::std::rc::Weak::new()"),
             }
         }
     }
 }
 
+fn sync() {
+    impl<T: BuildStr + ?Sized> BuildStr for ::std::sync::Arc<T> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let name = ::std::any::type_name::<Self>();
+            if let Some(ty) = self.as_ref().__unsized_type() {
+                return buildstr::__private::shared_from(::std::sync::Arc::as_ptr(self), name, "::std::sync::Arc", ty, || self.as_ref().to_build_expr());
+            }
+            match self.as_ref().__dyn_type() {
+                Some(ty) => buildstr::__private::shared_dyn(::std::sync::Arc::as_ptr(self), name, "::std::sync::Arc", ty, || self.as_ref().to_build_expr()),
+                None => buildstr::__private::shared(::std::sync::Arc::as_ptr(self), name, "::std::sync::Arc", || self.as_ref().to_build_expr()),
+            }
+        }
+    }
+    impl<T: BuildStr> BuildStr for ::std::sync::Arc<[T]> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let name = ::std::any::type_name::<Self>();
+            buildstr::__private::shared_from(::std::sync::Arc::as_ptr(self), name, "::std::sync::Arc", "[_]", || {
+                buildstr::__private::byte_sequence(buildstr::__private::Sequence::Array, T::__bytes(&self[..])).unwrap_or_else(|| {
+                    let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+                    buildstr::__private::sequence(buildstr::__private::Sequence::Array, elements)
+                })
+            })
+        }
+    }
+    impl<T: BuildStr> BuildStr for ::std::sync::Weak<T> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            match self.upgrade() {
+                Some(s) => {
+                    let name = ::std::any::type_name::<::std::sync::Arc<T>>();
+                    buildstr::__private::weak(::std::sync::Arc::as_ptr(&s), name, "::std::sync::Arc", "::std::sync::Weak", || s.as_ref().to_build_expr())
+                }
+                None => buildstr::Expr::path("::std::sync::Weak::new").call([]),
+            }
+        }
+    }
+    impl<T: BuildStr> BuildStr for ::std::sync::Mutex<T> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let ptr: *const Self = self;
+            buildstr::__private::visit(Some(ptr), ::std::any::type_name::<Self>(), || {
+                buildstr::__private::locked(self.try_lock(), |v| buildstr::Expr::path("::std::sync::Mutex::new").call([(**v).to_build_expr()]))
+            })
+        }
+    }
+    impl<T: BuildStr> BuildStr for ::std::sync::RwLock<T> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let ptr: *const Self = self;
+            buildstr::__private::visit(Some(ptr), ::std::any::type_name::<Self>(), || {
+                buildstr::__private::locked(self.try_read(), |v| buildstr::Expr::path("::std::sync::RwLock::new").call([(**v).to_build_expr()]))
+            })
+        }
+    }
+    impl<T: BuildStr> BuildStr for ::std::sync::OnceLock<T> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let new = buildstr::Expr::path("::std::sync::OnceLock::new").call([]);
+            if let Some(v) = self.get() {
+                buildstr::Expr::block(
+                    vec![
+                        buildstr::Stmt::Let { mutable: false, name: "lock".into(), ty: None, init: new },
+                        buildstr::Stmt::Let { mutable: false, name: "_".into(), ty: None, init: buildstr::Expr::path("lock").method("set", [v.to_build_expr()]) },
+                    ],
+                    buildstr::Expr::path("lock"),
+                )
+            } else {
+                new
+            }
+        }
+    }
+    // The value is initialized if it wasn't, as the function can't be rendered
+    impl<T: BuildStr, F: FnOnce() -> T> BuildStr for ::std::sync::LazyLock<T, F> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let init = buildstr::Expr::Closure { params: vec![], body: Box::new(::std::sync::LazyLock::force(self).to_build_expr()) };
+            buildstr::Expr::path("::std::sync::LazyLock::new").call([init])
+        }
+    }
+}
+
 fn array() {
     impl<T: BuildStr> BuildStr for &[T] {
-        fn to_build_string(&self) -> String {
-            format!("&[{}]", buildstr::array_to_build_string!(self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            if let Some(s) = buildstr::__private::byte_sequence(buildstr::__private::Sequence::Slice, T::__bytes(&self[..])) {
+                return s;
+            }
+            let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+            buildstr::__private::sequence(buildstr::__private::Sequence::Slice, elements)
         }
     }
     impl<T: BuildStr> BuildStr for &mut [T] {
-        fn to_build_string(&self) -> String {
-            format!("&mut [{}]", buildstr::array_to_build_string!(self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+            buildstr::__private::sequence(buildstr::__private::Sequence::SliceMut, elements)
         }
     }
     impl<T: BuildStr, const N: usize> BuildStr for [T; N] {
-        fn to_build_string(&self) -> String {
-            format!("[{}]", buildstr::array_to_build_string!(self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            if let Some(s) = buildstr::__private::byte_sequence(buildstr::__private::Sequence::Array, T::__bytes(&self[..])) {
+                return s;
+            }
+            let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+            buildstr::__private::sequence(buildstr::__private::Sequence::Array, elements)
         }
     }
 }
 
 fn vec() {
     impl<T: BuildStr> BuildStr for Vec<T> {
-        fn to_build_string(&self) -> String {
-            format!("::std::vec::Vec::from_iter([{}])", buildstr::array_to_build_string!(self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            if let Some(s) = buildstr::__private::byte_sequence(buildstr::__private::Sequence::Vec, T::__bytes(&self[..])) {
+                return s;
+            }
+            let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+            if let Some(items) = buildstr::__private::chunked(&elements) {
+                return items;
+            }
+            buildstr::__private::sequence(buildstr::__private::Sequence::Vec, elements)
         }
     }
 }
 
 fn tuple() {
     impl<A: BuildStr> BuildStr for (A,) {
-        fn to_build_string(&self) -> String {
-            format!("({},)", (&self.0).to_build_string())
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+            ])
         }
     }
     impl<A, B> BuildStr for (A, B) where A: BuildStr, B: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string()
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C> BuildStr for (A, B, C)
     where A: BuildStr, B: BuildStr, C: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string()
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C, D> BuildStr for (A, B, C, D)
     where A: BuildStr, B: BuildStr, C: BuildStr, D: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string(),
-                (&self.3).to_build_string()
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+                (&self.3).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C, D, E> BuildStr for (A, B, C, D, E)
     where A: BuildStr, B: BuildStr, C: BuildStr, D: BuildStr, E: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string(),
-                (&self.3).to_build_string(),
-                (&self.4).to_build_string()
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+                (&self.3).to_build_expr(),
+                (&self.4).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C, D, E, F> BuildStr for (A, B, C, D, E, F)
     where A: BuildStr, B: BuildStr, C: BuildStr, D: BuildStr, E: BuildStr, F: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {}, {}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string(),
-                (&self.3).to_build_string(),
-                (&self.4).to_build_string(),
-                (&self.5).to_build_string()
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+                (&self.3).to_build_expr(),
+                (&self.4).to_build_expr(),
+                (&self.5).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C, D, E, F, G> BuildStr for (A, B, C, D, E, F, G)
     where A: BuildStr, B: BuildStr, C: BuildStr, D: BuildStr, E: BuildStr, F: BuildStr, G: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {}, {}, {}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string(),
-                (&self.3).to_build_string(),
-                (&self.4).to_build_string(),
-                (&self.5).to_build_string(),
-                (&self.6).to_build_string()
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+                (&self.3).to_build_expr(),
+                (&self.4).to_build_expr(),
+                (&self.5).to_build_expr(),
+                (&self.6).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C, D, E, F, G, H> BuildStr for (A, B, C, D, E, F, G, H)
     where A: BuildStr, B: BuildStr, C: BuildStr, D: BuildStr, E: BuildStr, F: BuildStr, G: BuildStr, H: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {}, {}, {}, {}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string(),
-                (&self.3).to_build_string(),
-                (&self.4).to_build_string(),
-                (&self.5).to_build_string(),
-                (&self.6).to_build_string(),
-                (&self.7).to_build_string(),
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+                (&self.3).to_build_expr(),
+                (&self.4).to_build_expr(),
+                (&self.5).to_build_expr(),
+                (&self.6).to_build_expr(),
+                (&self.7).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C, D, E, F, G, H, I> BuildStr for (A, B, C, D, E, F, G, H, I)
     where A: BuildStr, B: BuildStr, C: BuildStr, D: BuildStr, E: BuildStr, F: BuildStr, G: BuildStr, H: BuildStr, I: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {}, {}, {}, {}, {}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string(),
-                (&self.3).to_build_string(),
-                (&self.4).to_build_string(),
-                (&self.5).to_build_string(),
-                (&self.6).to_build_string(),
-                (&self.7).to_build_string(),
-                (&self.8).to_build_string(),
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+                (&self.3).to_build_expr(),
+                (&self.4).to_build_expr(),
+                (&self.5).to_build_expr(),
+                (&self.6).to_build_expr(),
+                (&self.7).to_build_expr(),
+                (&self.8).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C, D, E, F, G, H, I, J> BuildStr for (A, B, C, D, E, F, G, H, I, J)
     where A: BuildStr, B: BuildStr, C: BuildStr, D: BuildStr, E: BuildStr, F: BuildStr, G: BuildStr, H: BuildStr, I: BuildStr, J: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {}, {}, {}, {}, {}, {}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string(),
-                (&self.3).to_build_string(),
-                (&self.4).to_build_string(),
-                (&self.5).to_build_string(),
-                (&self.6).to_build_string(),
-                (&self.7).to_build_string(),
-                (&self.8).to_build_string(),
-                (&self.9).to_build_string(),
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+                (&self.3).to_build_expr(),
+                (&self.4).to_build_expr(),
+                (&self.5).to_build_expr(),
+                (&self.6).to_build_expr(),
+                (&self.7).to_build_expr(),
+                (&self.8).to_build_expr(),
+                (&self.9).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C, D, E, F, G, H, I, J, K> BuildStr for (A, B, C, D, E, F, G, H, I, J, K)
     where A: BuildStr, B: BuildStr, C: BuildStr, D: BuildStr, E: BuildStr, F: BuildStr, G: BuildStr, H: BuildStr, I: BuildStr, J: BuildStr, K: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string(),
-                (&self.3).to_build_string(),
-                (&self.4).to_build_string(),
-                (&self.5).to_build_string(),
-                (&self.6).to_build_string(),
-                (&self.7).to_build_string(),
-                (&self.8).to_build_string(),
-                (&self.9).to_build_string(),
-                (&self.10).to_build_string(),
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+                (&self.3).to_build_expr(),
+                (&self.4).to_build_expr(),
+                (&self.5).to_build_expr(),
+                (&self.6).to_build_expr(),
+                (&self.7).to_build_expr(),
+                (&self.8).to_build_expr(),
+                (&self.9).to_build_expr(),
+                (&self.10).to_build_expr(),
+            ])
         }
     }
     impl<A, B, C, D, E, F, G, H, I, J, K, L> BuildStr for (A, B, C, D, E, F, G, H, I, J, K, L)
     where A: BuildStr, B: BuildStr, C: BuildStr, D: BuildStr, E: BuildStr, F: BuildStr, G: BuildStr, H: BuildStr, I: BuildStr, J: BuildStr, K: BuildStr, L: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!(
-                "({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {})",
-                (&self.0).to_build_string(),
-                (&self.1).to_build_string(),
-                (&self.2).to_build_string(),
-                (&self.3).to_build_string(),
-                (&self.4).to_build_string(),
-                (&self.5).to_build_string(),
-                (&self.6).to_build_string(),
-                (&self.7).to_build_string(),
-                (&self.8).to_build_string(),
-                (&self.9).to_build_string(),
-                (&self.10).to_build_string(),
-                (&self.11).to_build_string(),
-            )
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::tuple([
+                (&self.0).to_build_expr(),
+                (&self.1).to_build_expr(),
+                (&self.2).to_build_expr(),
+                (&self.3).to_build_expr(),
+                (&self.4).to_build_expr(),
+                (&self.5).to_build_expr(),
+                (&self.6).to_build_expr(),
+                (&self.7).to_build_expr(),
+                (&self.8).to_build_expr(),
+                (&self.9).to_build_expr(),
+                (&self.10).to_build_expr(),
+                (&self.11).to_build_expr(),
+            ])
         }
     }
 }
 
 fn reference() {
-    impl<T: BuildStr> BuildStr for &T {
-        fn to_build_string(&self) -> String {
-            format!("&{}", BuildStr::to_build_string(*self))
+    impl<T: BuildStr + ?Sized> BuildStr for &T {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            if (**self).__unsized_type().is_some() {
+                return BuildStr::to_build_expr(*self);
+            }
+            let reference = buildstr::__private::reference(false, BuildStr::to_build_expr(*self));
+            match (**self).__dyn_type() {
+                Some(ty) => reference.cast(format!("&{ty}")),
+                None => reference,
+            }
         }
     }
     impl<T: BuildStr> BuildStr for &mut T {
-        fn to_build_string(&self) -> String {
-            format!("&mut {}", BuildStr::to_build_string(*self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::__private::reference(true, BuildStr::to_build_expr(*self))
         }
     }
 }
 
 fn borrow() {
     impl<'a, T: ::std::borrow::ToOwned + ?Sized> BuildStr for ::std::borrow::Cow<'a, T> where <T as ToOwned>::Owned: BuildStr, &'a T: BuildStr {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            // `type_name` isn't a path, so the type is only written if it's made of primitives, like `str` or `[i32]`
             let ty = ::std::any::type_name::<T>();
+            let variant = |name: &str| if ty.contains("::") {
+                buildstr::Expr::path(&format!("::std::borrow::Cow::{name}"))
+            } else {
+                buildstr::Expr::path(&format!("::std::borrow::Cow::{name}::<{ty}>"))
+            };
             match self {
-                ::std::borrow::Cow::Borrowed(b) => format!("::std::borrow::Cow::Borrowed::<{ty}>({})", (*b).to_build_string()),
-                ::std::borrow::Cow::Owned(o) => format!("::std::borrow::Cow::Owned::<{ty}>({})", o.to_build_string()),
+                ::std::borrow::Cow::Borrowed(b) => variant("Borrowed").call([(*b).to_build_expr()]),
+                ::std::borrow::Cow::Owned(o) => variant("Owned").call([o.to_build_expr()]),
             }
         }
     }
@@ -618,42 +1070,49 @@ fn borrow() {
 
 fn cell() {
     impl <T: BuildStr> BuildStr for ::core::cell::Cell<T> {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
             let v = self.as_ptr();
-            // SAFETY: The pointer must be valid, as the cell is always initialized
-            if let Some(v) = unsafe { v.as_ref() } {
-                format!("::core::cell::Cell::new({})", (&v).to_build_string())
-            } else {
-                panic!("Invalid pointer in ::core::cell::Cell, can't convert to BuildStr");
-            }
+            buildstr::__private::visit(Some(v.cast_const()), ::std::any::type_name::<Self>(), || {
+                // SAFETY: The pointer must be valid, as the cell is always initialized
+                if let Some(v) = unsafe { v.as_ref() } {
+                    buildstr::Expr::path("::core::cell::Cell::new").call([(&v).to_build_expr()])
+                } else {
+                    panic!("Invalid pointer in ::core::cell::Cell, can't convert to BuildStr");
+                }
+            })
         }
     }
     // TODO: Needs testing
     impl <T: BuildStr> BuildStr for ::core::cell::OnceCell<T> {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let new = buildstr::Expr::path("::core::cell::OnceCell::new").call([]);
             if let Some(v) = self.get() {
-                format!("{{
-                    let cell = ::core::cell::OnceCell::new(); 
-                    let _ = cell.set({});
-                    cell
-                }}", v.to_build_string())
+                buildstr::Expr::block(
+                    vec![
+                        buildstr::Stmt::Let { mutable: false, name: "cell".into(), ty: None, init: new },
+                        buildstr::Stmt::Let { mutable: false, name: "_".into(), ty: None, init: buildstr::Expr::path("cell").method("set", [v.to_build_expr()]) },
+                    ],
+                    buildstr::Expr::path("cell"),
+                )
             } else {
-                "::core::cell::OnceCell::new()".into()
+                new
             }
         }
     }
     impl <T: BuildStr> BuildStr for ::core::cell::RefCell<T> {
-        fn to_build_string(&self) -> String {
-            format!("::core::cell::RefCell::new({})", self.borrow().to_build_string())
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::__private::visit(Some(self.as_ptr().cast_const()), ::std::any::type_name::<Self>(), || {
+                buildstr::Expr::path("::core::cell::RefCell::new").call([self.borrow().to_build_expr()])
+            })
         }
     }
     // TODO: Needs testing
     impl <T: BuildStr> BuildStr for ::core::cell::UnsafeCell<T> {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
             let v = self.get();
             // SAFETY: The pointer must be valid, as the cell is always initialized
             if let Some(v) = unsafe { v.as_ref() } {
-                format!("::core::cell::UnsafeCell::new({})", v.to_build_string())
+                buildstr::Expr::path("::core::cell::UnsafeCell::new").call([v.to_build_expr()])
             } else {
                 panic!("Invalid pointer in ::core::cell::UnsafeCell, can't convert to BuildStr");
             }
@@ -663,56 +1122,110 @@ fn cell() {
 
 fn collections() {
     impl<K, V> BuildStr for ::std::collections::BTreeMap<K, V> where K: BuildStr + ::core::cmp::Ord, V: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!("::std::collections::BTreeMap::from_iter([{}])", buildstr::map_to_build_string!(self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let entries = buildstr::__private::render_elements(self.iter(), |(k, v)| {
+                buildstr::Expr::tuple([buildstr::__private::dedup(k, k.to_build_expr()), buildstr::__private::dedup(v, v.to_build_expr())])
+                    .with_layout(buildstr::Layout::Compact)
+            });
+            let items = match buildstr::__private::chunked(&entries) {
+                Some(items) => items,
+                None => buildstr::Expr::Array(entries),
+            };
+            buildstr::Expr::path("::std::collections::BTreeMap::from_iter").call([items])
         }
     }
     impl<T: BuildStr> BuildStr for ::std::collections::BTreeSet<T> {
-        fn to_build_string(&self) -> String {
-            format!("::std::collections::BTreeSet::from_iter([{}])", buildstr::array_to_build_string!(self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+            buildstr::Expr::path("::std::collections::BTreeSet::from_iter").call([buildstr::Expr::Array(elements)])
         }
     }
     impl<T: BuildStr> BuildStr for ::std::collections::BinaryHeap<T> {
-        fn to_build_string(&self) -> String {
-            format!("::std::collections::BinaryHeap::from_iter([{}])", buildstr::array_to_build_string!(self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+            buildstr::Expr::path("::std::collections::BinaryHeap::from_iter").call([buildstr::Expr::Array(elements)])
         }
     }
-    impl<K, V, S> BuildStr for ::std::collections::HashMap<K, V, S> where K: BuildStr, V: BuildStr {
-        fn to_build_string(&self) -> String {
-            format!("::std::collections::HashMap::from_iter([{}])", buildstr::map_to_build_string!(self))
+    impl<K, V, S> BuildStr for ::std::collections::HashMap<K, V, S> where K: BuildStr, V: BuildStr, S: BuildStr {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let mut map = self.iter().map(|(k, v)| (buildstr::__private::dedup(k, k.to_build_expr()), buildstr::__private::dedup(v, v.to_build_expr()))).collect::<Vec<_>>();
+            if buildstr::__private::deterministic() {
+                map.sort_by_cached_key(|(k, v)| (buildstr::__private::sort_key(k), buildstr::__private::sort_key(v)));
+            }
+            let map = map.into_iter().map(|(k, v)| buildstr::Expr::tuple([k, v]).with_layout(buildstr::Layout::Compact)).collect::<Vec<_>>();
+            let map = match buildstr::__private::chunked(&map) {
+                Some(items) => items,
+                None => buildstr::Expr::Array(map),
+            };
+            let len = self.len();
+            let new = buildstr::Expr::path("::std::collections::HashMap::with_capacity_and_hasher")
+                .call([buildstr::Expr::Lit(len.to_string()), self.hasher().to_build_expr()]);
+            if len == 0 {
+                // The type of the entries of an empty `extend` can't be inferred
+                return new;
+            }
+            buildstr::Expr::block(
+                vec![
+                    buildstr::Stmt::Let { mutable: true, name: "map".into(), ty: None, init: new },
+                    buildstr::Stmt::Expr(buildstr::Expr::path("map").method("extend", [map])),
+                ],
+                buildstr::Expr::path("map"),
+            )
         }
     }
-    impl<T: BuildStr, S> BuildStr for ::std::collections::HashSet<T, S> {
-        fn to_build_string(&self) -> String {
-            format!("::std::collections::HashSet::from_iter([{}])", buildstr::array_to_build_string!(self))
+    impl<T, S> BuildStr for ::std::collections::HashSet<T, S> where T: BuildStr, S: BuildStr {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let mut set = self.iter().map(|t| buildstr::__private::dedup(t, t.to_build_expr())).collect::<Vec<_>>();
+            if buildstr::__private::deterministic() {
+                set.sort_by_cached_key(|t| buildstr::__private::sort_key(t));
+            }
+            let len = self.len();
+            let new = buildstr::Expr::path("::std::collections::HashSet::with_capacity_and_hasher")
+                .call([buildstr::Expr::Lit(len.to_string()), self.hasher().to_build_expr()]);
+            if len == 0 {
+                return new;
+            }
+            buildstr::Expr::block(
+                vec![
+                    buildstr::Stmt::Let { mutable: true, name: "set".into(), ty: None, init: new },
+                    buildstr::Stmt::Expr(buildstr::Expr::path("set").method("extend", [buildstr::Expr::Array(set)])),
+                ],
+                buildstr::Expr::path("set"),
+            )
         }
     }
     impl<T: BuildStr> BuildStr for ::std::collections::LinkedList<T> {
-        fn to_build_string(&self) -> String {
-            format!("::std::collections::LinkedList::from_iter([{}])", buildstr::array_to_build_string!(self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+            buildstr::Expr::path("::std::collections::LinkedList::from_iter").call([buildstr::Expr::Array(elements)])
         }
     }
     impl<T: BuildStr> BuildStr for ::std::collections::VecDeque<T> {
-        fn to_build_string(&self) -> String {
-            format!("::std::collections::VecDeque::from_iter([{}])", buildstr::array_to_build_string!(self))
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let elements = buildstr::__private::render_elements(self.iter(), |x| buildstr::__private::dedup(x, x.to_build_expr()));
+            match buildstr::__private::chunked(&elements) {
+                Some(items) => buildstr::Expr::path("::std::collections::VecDeque::from").call([items]),
+                None => buildstr::Expr::path("::std::collections::VecDeque::from_iter").call([buildstr::Expr::Array(elements)]),
+            }
         }
     }
 }
 
 fn cmp() {
     impl<T: BuildStr> BuildStr for ::core::cmp::Reverse<T> {
-        fn to_build_string(&self) -> String {
-            format!("::core::cmp::Reverse({})", self.0.to_build_string())
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::path("::core::cmp::Reverse").call([self.0.to_build_expr()])
         }
     }
 }
 
 fn convert() {
     impl<T: ?Sized> BuildStr for &dyn std::convert::AsRef<T> where for<'a> &'a T: BuildStr {
-        fn to_build_string(&self) -> String {
-            let r = self.as_ref().to_build_string();
+        fn to_build_expr(&self) -> buildstr::Expr {
+            // The type of the reference is erased by the cast
+            let r = buildstr::__private::typed(false, || self.as_ref().to_build_expr());
             let ty = std::any::type_name::<T>();
-            format!("&{r} as &dyn ::std::convert::AsRef<{ty}>")
+            r.reference(false).cast(format!("&dyn ::std::convert::AsRef<{ty}>"))
         }
     }
 }
@@ -727,32 +1240,33 @@ fn fmt() {
 
 fn future() {
     impl<T> BuildStr for ::core::future::Pending<T> {
-        fn to_build_string(&self) -> String {
-            format!("::core::future::pending::<{}>()", ::core::any::type_name::<T>())
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::path(&format!("::core::future::pending::<{}>", ::core::any::type_name::<T>())).call([])
         }
     }
 }
 
 fn hash() {
-    impl<H> BuildStr for ::core::hash::BuildHasherDefault<H> {
-        fn to_build_string(&self) -> String {
-            format!("{}::default()", ::std::any::type_name::<Self>())
+    impl<H: 'static> BuildStr for ::core::hash::BuildHasherDefault<H> {
+        fn to_build_expr(&self) -> buildstr::Expr {
+            // `type_name` isn't always a valid path, so the hasher is inferred unless an override writes it
+            buildstr::__private::render_static::<Self>(self, || buildstr::Expr::path("::core::hash::BuildHasherDefault::default").call([]))
         }
     }
 }
 
 fn marker() {
     impl<T> BuildStr for ::core::marker::PhantomData<T> {
-        fn to_build_string(&self) -> String {
-            format!("::core::marker::PhantomData::<{}>", ::std::any::type_name::<T>())
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::path(&format!("::core::marker::PhantomData::<{}>", ::std::any::type_name::<T>()))
         }
     }
 }
 
 fn mem() {
     impl<T: BuildStr> BuildStr for ::core::mem::ManuallyDrop<T> {
-        fn to_build_string(&self) -> String {
-            format!("::core::mem::ManuallyDrop::new({})", (**self).to_build_string())
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::path("::core::mem::ManuallyDrop::new").call([(**self).to_build_expr()])
         }
     }
 }
@@ -763,78 +1277,115 @@ fn net() {
 
 fn num() {
     impl<T: BuildStr> BuildStr for ::core::num::Wrapping<T> {
-        fn to_build_string(&self) -> String {
-            format!("::core::num::Wrapping({})", self.0.to_build_string())
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::path("::core::num::Wrapping").call([self.0.to_build_expr()])
         }
     }
 }
 
 fn ops() {
     impl<T: BuildStr> BuildStr for ::core::ops::Bound<T> {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
             match self {
-                ::core::ops::Bound::Included(i) => format!("::core::ops::Bound::Included({})", i.to_build_string()),
-                ::core::ops::Bound::Excluded(e) => format!("::core::ops::Bound::Excluded({})", e.to_build_string()),
-                ::core::ops::Bound::Unbounded => "::core::ops::Bound::Unbounded".into(),
+                ::core::ops::Bound::Included(i) => buildstr::Expr::path("::core::ops::Bound::Included").call([i.to_build_expr()]),
+                ::core::ops::Bound::Excluded(e) => buildstr::Expr::path("::core::ops::Bound::Excluded").call([e.to_build_expr()]),
+                ::core::ops::Bound::Unbounded => buildstr::Expr::path("::core::ops::Bound::Unbounded"),
             }
         }
     }
     impl<B, C> BuildStr for ::core::ops::ControlFlow<B, C> where B: BuildStr, C: BuildStr {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
             match self {
-                ::core::ops::ControlFlow::Continue(c) => format!("::core::ops::ControlFlow::Continue({})", c.to_build_string()),
-                ::core::ops::ControlFlow::Break(b) => format!("::core::ops::ControlFlow::Break({})", b.to_build_string()),
+                ::core::ops::ControlFlow::Continue(c) => buildstr::Expr::path("::core::ops::ControlFlow::Continue").call([c.to_build_expr()]),
+                ::core::ops::ControlFlow::Break(b) => buildstr::Expr::path("::core::ops::ControlFlow::Break").call([b.to_build_expr()]),
             }
         }
     }
     impl<Idx: BuildStr> BuildStr for ::core::ops::Range<Idx> {
-        fn to_build_string(&self) -> String {
-            let start = self.start.to_build_string();
-            let end = self.end.to_build_string();
-            format!("::core::ops::Range{{start:{start},end:{end}}}")
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let start = self.start.to_build_expr();
+            let end = self.end.to_build_expr();
+            if buildstr::__private::idiomatic() {
+                buildstr::Expr::Range { start: Some(Box::new(start)), end: Some(Box::new(end)), inclusive: false }
+            } else {
+                buildstr::Expr::StructLit {
+                    path: buildstr::Path::parse("::core::ops::Range"),
+                    fields: vec![("start".into(), start), ("end".into(), end)],
+                    layout: buildstr::Layout::Compact,
+                }
+            }
         }
     }
     impl<Idx: BuildStr> BuildStr for ::core::ops::RangeFrom<Idx> {
-        fn to_build_string(&self) -> String {
-            let start = self.start.to_build_string();
-            format!("::core::ops::RangeFrom{{start:{start}}}")
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let start = self.start.to_build_expr();
+            if buildstr::__private::idiomatic() {
+                buildstr::Expr::Range { start: Some(Box::new(start)), end: None, inclusive: false }
+            } else {
+                buildstr::Expr::StructLit {
+                    path: buildstr::Path::parse("::core::ops::RangeFrom"),
+                    fields: vec![("start".into(), start)],
+                    layout: buildstr::Layout::Compact,
+                }
+            }
         }
     }
     impl<Idx: BuildStr> BuildStr for ::core::ops::RangeInclusive<Idx> {
-        fn to_build_string(&self) -> String {
-            let start = self.start().to_build_string();
-            let end = self.end().to_build_string();
-            format!("::core::ops::RangeInclusive{{start:{start},end:{end}}}")
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let start = self.start().to_build_expr();
+            let end = self.end().to_build_expr();
+            // The fields are private, so it can only be built with `new`
+            if buildstr::__private::idiomatic() {
+                buildstr::Expr::Range { start: Some(Box::new(start)), end: Some(Box::new(end)), inclusive: true }
+            } else {
+                buildstr::Expr::path("::core::ops::RangeInclusive::new").call([start, end])
+            }
         }
     }
     impl<Idx: BuildStr> BuildStr for ::core::ops::RangeTo<Idx> {
-        fn to_build_string(&self) -> String {
-            let end = self.end.to_build_string();
-            format!("::core::ops::RangeTo {{end:{end}}}")
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let end = self.end.to_build_expr();
+            if buildstr::__private::idiomatic() {
+                buildstr::Expr::Range { start: None, end: Some(Box::new(end)), inclusive: false }
+            } else {
+                buildstr::Expr::StructLit {
+                    path: buildstr::Path::parse("::core::ops::RangeTo"),
+                    fields: vec![("end".into(), end)],
+                    layout: buildstr::Layout::Compact,
+                }
+            }
         }
     }
     impl<Idx: BuildStr> BuildStr for ::core::ops::RangeToInclusive<Idx> {
-        fn to_build_string(&self) -> String {
-            let end = self.end.to_build_string();
-            format!("::core::ops::RangeToInclusive{{end:{end}}}")
+        fn to_build_expr(&self) -> buildstr::Expr {
+            let end = self.end.to_build_expr();
+            if buildstr::__private::idiomatic() {
+                buildstr::Expr::Range { start: None, end: Some(Box::new(end)), inclusive: true }
+            } else {
+                buildstr::Expr::StructLit {
+                    path: buildstr::Path::parse("::core::ops::RangeToInclusive"),
+                    fields: vec![("end".into(), end)],
+                    layout: buildstr::Layout::Compact,
+                }
+            }
         }
     }
 }
 
 fn panic() {
     impl<T: BuildStr> BuildStr for ::core::panic::AssertUnwindSafe<T> {
-        fn to_build_string(&self) -> String {
-            format!("::core::panic::AssertUnwindSafe({})", self.0.to_build_string())
+        fn to_build_expr(&self) -> buildstr::Expr {
+            buildstr::Expr::path("::core::panic::AssertUnwindSafe").call([self.0.to_build_expr()])
         }
     }
 }
 
 fn pin() {
     impl<T: Unpin + BuildStr + ::core::ops::Deref> BuildStr for ::std::pin::Pin<T> {
-        fn to_build_string(&self) -> String {
+        fn to_build_expr(&self) -> buildstr::Expr {
             // SAFETY: ::std::pin::Pin<T> is repr(transparent), so we can safely downcast it
             let ptr: &T = unsafe { &*(self as *const std::pin::Pin<T>).cast() };
-            format!("::std::pin::Pin::new({})", ptr.to_build_string())
+            buildstr::Expr::path("::std::pin::Pin::new").call([ptr.to_build_expr()])
         }
     }
 }